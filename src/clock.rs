@@ -0,0 +1,58 @@
+//! Where the current time comes from. Anything that needs to know what time
+//! it is takes a `Clock`, so it can run somewhere `SystemTime` doesn't work,
+//! like in a browser, or on a made-up timeline, like in tests.
+
+use std::cell::Cell;
+
+pub trait Clock {
+    /// Milliseconds since the UNIX epoch.
+    fn now(&self) -> u128;
+}
+
+/// The real time. Uses `Date.now()` when running in a browser.
+///
+/// `SystemTime` panics on wasm32, so there it needs the `wasm` feature and
+/// doesn't exist without it. Use another `Clock` there instead.
+#[cfg(any(not(target_arch = "wasm32"), feature = "wasm"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "wasm"))]
+impl Clock for SystemClock {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn now(&self) -> u128 {
+        use std::time::SystemTime;
+        SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn now(&self) -> u128 {
+        js_sys::Date::now() as u128
+    }
+}
+
+/// A clock that only moves when it's told to.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Cell<u128>,
+}
+
+impl ManualClock {
+    pub fn new(now: u128) -> Self {
+        ManualClock { now: Cell::new(now) }
+    }
+
+    pub fn set(&self, now: u128) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, millis: u128) {
+        self.now.set(self.now.get() + millis);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> u128 {
+        self.now.get()
+    }
+}
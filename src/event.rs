@@ -0,0 +1,67 @@
+//! Structured game events. These are the machine-readable facts of what
+//! happened during a game. `Message`s are rendered from these, so anything
+//! that needs to know what happened (stats, replays, analytics) should read
+//! these instead of the message text.
+
+use crate::messaging::BallsStrikes;
+use crate::weather::Bird;
+
+pub type PlayerId = u32;
+pub type TeamId = u32;
+
+/// What happened. Player ids that aren't already part of the `Event`
+/// context (like the defender on a flyout or a baserunner) live here.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventKind {
+    StartGame,
+    InningStart,
+    NextBatter,
+    Ball,
+    Walk,
+    StrikeLooking,
+    StruckOutLooking,
+    StrikeSwinging,
+    StruckOutSwinging,
+    FoulBall,
+    Flyout { defender: PlayerId },
+    Groundout { defender: PlayerId },
+    Hit { bases: usize }, // More bases than the rules have is a home run
+    Steal { runner: PlayerId, base: usize }, // Base the runner stole from
+    CaughtStealing { runner: PlayerId, base: usize }, // Base the runner stole from
+    Advance { runner: PlayerId, from: usize, to: usize },
+    Score { runner: PlayerId },
+    Out,
+    InningToOuting,
+    GameOver { winner: TeamId, loser: TeamId },
+
+    // Weather
+    Crabs,
+    Incineration { victim: PlayerId, replacement: PlayerId },
+    Birds { bird: Bird },
+
+    // Modifiers
+    FireEater { player: PlayerId }, // Ate the fire instead of being incinerated
+    Flicker { runner: PlayerId }, // Flickered away from the tag, turning a caught stealing into a steal
+    Reverberating { player: PlayerId }, // Bats again
+}
+
+/// A single thing that happened in a game, along with the state of the game
+/// around it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Event {
+    pub kind: EventKind,
+
+    pub inning: u32,
+    pub top: bool,
+    pub batting_team: TeamId,
+    pub pitching_team: TeamId,
+    pub pitcher: PlayerId, // The pitcher on the mound
+    pub batter: PlayerId, // The batter at the plate
+
+    pub count_before: BallsStrikes,
+    pub count_after: BallsStrikes,
+    pub outs: i32, // Outs after the event
+    pub score_delta: (f64, f64), // Home, Away
+}
@@ -1,17 +1,46 @@
-use std::thread::{sleep, self};
-use std::time::{Duration, SystemTime};
-
-use crate::messaging::{MessageLog, Message};
+use crate::box_score::BoxScore;
+use crate::clock::Clock;
+use crate::event::{Event, EventKind, PlayerId, TeamId};
+use crate::messaging::{BallsStrikes, MessageLog, Message};
+use crate::modifiers::{ModifierDuration, ModifierKind};
 // The point of this file is to generate most of the game logic so it can be easily called via a functional interface.
 use crate::team::Team;
 use crate::player::Player;
-use crate::java_random::Random;
-
-const TICK: u128 = 0;
+use crate::rng::{GameRng, RngBackend, RngKind};
+use crate::seed::{Seed, Subsystem};
+use crate::rules::Rules;
+use crate::statistics::StatSheet;
+use crate::weather::Weather;
+
+const TICK: u128 = 2_000; // How long after the previous message each message shows up, in milliseconds
+
+/// Where a `Game` is in its progression. Each call to `Game::step()` runs
+/// the current phase and moves on to the next one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase {
+    /// The game hasn't started yet. Pitchers are picked when this runs.
+    Pregame,
+    /// A half inning is about to start.
+    HalfInningStart,
+    /// A new batter is stepping up to the plate.
+    PlateAppearance,
+    /// The batter is at the plate and the next pitch is about to be thrown.
+    Pitch,
+    /// A half inning just ended.
+    HalfInningEnd,
+    /// The last out was made and the final score needs to be announced.
+    Postgame,
+    /// Nothing left to do.
+    Over,
+}
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
-    pub rng: Random,
+    pub rng: RngBackend,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Rules,
 
     pub home: Team,
     pub away: Team,
@@ -21,9 +50,11 @@ pub struct Game {
 
     pub day: usize,
     pub start_time: u128, // NOTE: I feel like this could be collapsed into a single field? Like a start_time current_time struct.
-    pub current_time: u128,
+    pub current_time: u128, // When the latest message shows up
     pub message_log: MessageLog,
-    // pub weather: Weather, // TODO: implement weather
+    pub events: Vec<Event>,
+    pub statistics: StatSheet, // Stats for this game only
+    pub weather: Weather,
 
     pub inning: u32,
     pub top: bool,
@@ -40,34 +71,82 @@ pub struct Game {
     pub away_bat: usize, // Wait no it can't. It's a u32.
 
     pub bases: Vec<Option<Player>>,
+
+    pub incinerated: Vec<Player>, // Players incinerated during this game
+
+    pub phase: Phase,
 }
 
 impl Game {
-    pub fn new(home: Team, away: Team, day: usize, start_time: u128) -> Self {
-        let seed: i64 = (day + 4 + home.get_favor() as usize + away.get_favor() as usize) as i64; // Changes in update. TODO: fix this
+    /// Creates a game with a seed worked out from the day and the two teams,
+    /// so no two games on the same day share one. Games on a league's
+    /// schedule are seeded from the league's seed instead, see `League::game_seed()`.
+    ///
+    /// # Panics
+    /// If `rules` don't pass `Rules::validate()`, this function panics. So do
+    /// the other constructors.
+    pub fn new(home: Team, away: Team, day: usize, start_time: u128, rules: Rules) -> Self {
+        let seed = Game::seed_for(day, &home, &away);
+        Game::with_seed(home, away, day, start_time, rules, seed)
+    }
+
+    /// The seed `new()` gives a game between `home` and `away` on `day`.
+    pub fn seed_for(day: usize, home: &Team, away: &Team) -> i64 {
+        Seed::new(0).day(day).matchup(home.get_id(), away.get_id()).value()
+    }
+
+    /// The seed the Java version gives a game, for checking games against it.
+    /// Teams with the same favor get the same seed on the same day.
+    pub fn java_seed(day: usize, home: &Team, away: &Team) -> i64 {
+        (day + 4 + home.get_favor() as usize + away.get_favor() as usize) as i64
+    }
+
+    /// Works the same as `new()`, but uses the given seed instead of working one out from the day and the teams.
+    /// The weather gets its own seed derived from this one.
+    pub fn with_seed(home: Team, away: Team, day: usize, start_time: u128, rules: Rules, seed: i64) -> Self {
+        Game::with_rng_kind(home, away, day, start_time, rules, RngKind::Java, seed)
+    }
+
+    /// Works the same as `with_seed()`, but rolls with the given kind of RNG.
+    pub fn with_rng_kind(home: Team, away: Team, day: usize, start_time: u128, rules: Rules, kind: RngKind, seed: i64) -> Self {
+        let weather = Weather::from_rng(kind.seeded(Seed::new(seed).subsystem(Subsystem::Weather).value()));
+        Game::with_rng(home, away, day, start_time, rules, kind.seeded(seed), weather)
+    }
 
-        let mut rng = Random::new(seed);
+    /// Creates a game that rolls with exactly the RNG and weather it's given,
+    /// like a `RecordingRng` with scripted draws.
+    pub fn with_rng(home: Team, away: Team, day: usize, start_time: u128, rules: Rules, rng: RngBackend, weather: Weather) -> Self {
+        if let Err(error) = rules.validate() {
+            panic!("can't play a game with these rules: {}", error);
+        }
+
+        // Placeholders don't roll with the game's RNG, so recordings
+        // and scripts only ever see the game's own draws
+        let mut placeholders = RngBackend::default();
         Game {
             rng,
+            rules,
 
             home,
             away,
 
             // Placeholders
-            home_pitcher: Player::default(&mut rng),
-            away_pitcher: Player::default(&mut rng),
+            home_pitcher: Player::default(&mut placeholders),
+            away_pitcher: Player::default(&mut placeholders),
         
             day,
             start_time,
-            current_time: 0,
+            current_time: start_time,
             message_log: MessageLog::new(),
-            // weather: , // TODO: implement weather
+            events: Vec::new(),
+            statistics: StatSheet::new(),
+            weather,
         
             inning: 1,
             top: true,
         
-            batter: Player::default(&mut rng), // Placeholder
-            defender: Player::default(&mut rng), // Placeholder
+            batter: Player::default(&mut placeholders), // Placeholder
+            defender: Player::default(&mut placeholders), // Placeholder
         
             scores: (0.0, 0.0),
             wins: (0, 0),
@@ -77,7 +156,11 @@ impl Game {
             home_bat: 0,
             away_bat: 0,
         
-            bases: Vec::from([None, None, None]),
+            bases: vec![None; rules.bases],
+
+            incinerated: Vec::new(),
+
+            phase: Phase::Pregame,
         }
     }
 
@@ -183,86 +266,136 @@ impl Game {
 
     // Figure out a better way to do this
     pub fn start_game(&mut self) {
-        let len_home = &self.home.get_active_pitchers().len();
-        let pitcher_home = &self.home
-            .get_active_pitchers()[(self.day - 1 + len_home) % len_home];
-        let len_away = &self.away.get_active_pitchers().len();
-        let pitcher_away = &self.away
-            .get_active_pitchers()[(self.day - 1 + len_away) % len_away].clone();
-        
-        self.home_pitcher = pitcher_home.clone();
-        self.away_pitcher = pitcher_away.clone();
+        self.home.order_lineup(&mut self.rng);
+        self.away.order_lineup(&mut self.rng);
+        self.home_pitcher = self.pick_pitcher(&self.home);
+        self.away_pitcher = self.pick_pitcher(&self.away);
     }
 
-    // Placeholder
-    pub fn simulate_game(&mut self) {
-        self.start_game();
-
-        self.log(Message::StartGame);
-
-        let sys_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
-        
-        let cur_time = sys_time.as_secs();
+    // Pitchers take turns by day. Anyone who can't play
+    // passes their turn on to the next pitcher in the
+    // rotation. If nobody can, the one whose turn it is
+    // pitches anyway.
+    fn pick_pitcher(&self, team: &Team) -> Player {
+        let pitchers = team.get_active_pitchers();
+        let len = pitchers.len();
+        (0..len)
+            .map(|offset| &pitchers[(self.day - 1 + len + offset) % len])
+            .find(|pitcher| self.modifiers_of(pitcher.id).iter().all(ModifierKind::can_play))
+            .unwrap_or(&pitchers[(self.day - 1 + len) % len])
+            .clone()
+    }
 
-        while !self.is_game_over() {
-            self.do_inning();
+    /// Runs the whole game to completion.
+    pub fn simulate_game(&mut self) {
+        while !self.is_over() {
+            self.step();
         }
-
-        self.log(Message::EndGameScore(self.home.get_name(), self.scores.0, self.away.get_name(), self.scores.1));
-        self.log(Message::GameOver);
-        self.give_wins();
     }
 
-    // Placeholder
-    pub fn do_inning(&mut self) {
-        self.log(Message::InningStart(self.top, self.inning, self.teams_batting().get_name(), self.teams_pitching().get_name()));
-
-        while !self.is_inning_over() {
-            self.clear_bases();
-            self.set_next_batter();
-            while !self.has_struck_out() {
+    /// Advances the game by a single phase (usually a single pitch) and
+    /// returns the events that happened while doing so.
+    ///
+    /// Once the game is over, this does nothing and returns an empty `Vec`.
+    pub fn step(&mut self) -> Vec<Event> {
+        let emitted = self.events.len();
+
+        match self.phase {
+            Phase::Pregame => {
+                self.start_game();
+                self.emit(EventKind::StartGame);
+                self.weather_hook(Weather::start_of_game);
+                self.phase = Phase::HalfInningStart;
+            },
+            Phase::HalfInningStart => {
+                if self.top {
+                    self.weather_hook(Weather::before_full_inning);
+                }
+                self.weather_hook(Weather::before_half_inning);
+                self.emit(EventKind::InningStart);
+                self.phase = Phase::PlateAppearance;
+            },
+            Phase::PlateAppearance => {
+                self.clear_bases();
+                self.set_next_batter();
+                self.phase = Phase::Pitch;
+            },
+            Phase::Pitch => {
+                self.weather_hook(Weather::before_pitch);
                 self.do_steals();
                 self.do_pitch();
-            }
-            self.strikes = 0;
-            self.balls = 0;
-            self.outs += 1;
-            self.log_with_time(Message::Out(self.outs), 0);
-        }
-
-        // Do end of inning stuff
-        self.outs = 0;
-        match self.top {
-            true => self.top = false,
-            false => {
-                self.top = true;
-                self.log(Message::InningToOuting(self.inning));
-                self.print_score(); // NOTE: I don't like this
-
-                self.inning += 1;
+                if self.has_struck_out() {
+                    self.strikes = 0;
+                    self.balls = 0;
+                    self.outs += 1;
+                    self.emit(EventKind::Out);
+
+                    if self.is_inning_over() {
+                        self.phase = Phase::HalfInningEnd;
+                    } else {
+                        self.phase = Phase::PlateAppearance;
+                    }
+                }
             },
+            Phase::HalfInningEnd => {
+                self.outs = 0;
+                self.expire_modifiers(ModifierDuration::Inning);
+                match self.top {
+                    true => self.top = false,
+                    false => {
+                        self.emit(EventKind::InningToOuting);
+                        self.top = true;
+                        self.print_score(); // NOTE: I don't like this
+
+                        self.inning += 1;
+                    },
+                }
+                if self.is_game_over() {
+                    self.phase = Phase::Postgame;
+                } else {
+                    self.phase = Phase::HalfInningStart;
+                }
+            },
+            Phase::Postgame => {
+                self.log(Message::EndGameScore(self.home.get_name(), self.scores.0, self.away.get_name(), self.scores.1));
+                let (winner, loser) = if self.scores.0 > self.scores.1 {
+                    (self.home.get_id(), self.away.get_id())
+                } else {
+                    (self.away.get_id(), self.home.get_id())
+                };
+                self.emit(EventKind::GameOver { winner, loser });
+                self.give_wins();
+                self.give_statistics();
+                self.expire_modifiers(ModifierDuration::Game);
+                self.weather_hook(Weather::end_of_game);
+                self.phase = Phase::Over;
+            },
+            Phase::Over => {},
         }
+
+        self.events[emitted..].to_vec()
+    }
+
+    /// Returns an iterator that steps through the game, yielding the
+    /// events of every step until the game is over.
+    pub fn steps(&mut self) -> Steps<'_> {
+        Steps { game: self }
+    }
+
+    /// Returns true once the final score has been logged.
+    pub fn is_over(&self) -> bool {
+        self.phase == Phase::Over
     }
 
     // NOTE: This feels off idk. Figure out a better way to do this
     pub fn is_game_over(&self) -> bool {
-        self.scores.0 != self.scores.1 && self.inning > 9 && self.top == true
+        self.scores.0 != self.scores.1 && self.inning > self.rules.innings && self.top
     }
 
     pub fn is_inning_over(&self) -> bool {
-        self.outs >= 3
+        self.outs >= self.rules.outs
     }
 
-    // pub fn next_pitch(&mut self) -> Vec<GameLog> {
-    //     let mut logs = Vec::new();
-    //     let mut rng = self.rng.clone();
-
-        
-        
-    //     self.rng = rng;
-    //     logs
-    // }
-    
     /// Appends a `Message` log to the `MessageLog` struct. It uses the default `TICK` value for the time and assumes that the message isn't special.
     pub fn log(&mut self, log: Message) {
         self.log_full_event(log, TICK, false);
@@ -274,16 +407,150 @@ impl Game {
     }
 
     /// Takes all values in the `MessageLog` struct and appends them in.
+    /// `time` is how long after the previous message this one shows up.
     pub fn log_full_event(&mut self, log: Message, time: u128, is_special: bool) {
-        // Unused since Duration can sleep for x amount of time (which is a cursed solution but I'll find a better one later)
-        let current_time = self.current_time; // See if there's a better way to handle current time?
-        self.message_log.log(log, time, is_special);
         self.current_time += time;
+        self.message_log.log(log, self.current_time, is_special);
+    }
+
+    /// Records an `Event` and logs the `Message` it renders to. Uses the current count as the count before the event.
+    pub fn emit(&mut self, kind: EventKind) {
+        self.emit_with_count(kind, (self.balls, self.strikes));
+    }
+
+    /// Works the same as the `emit()` function, but takes the count from before the event happened.
+    pub fn emit_with_count(&mut self, kind: EventKind, count_before: BallsStrikes) {
+        self.emit_full_event(kind, count_before, (0.0, 0.0));
+    }
+
+    /// Builds the `Event` from the current state of the game, renders its `Message`, and stores both.
+    pub fn emit_full_event(&mut self, kind: EventKind, count_before: BallsStrikes, score_delta: (f64, f64)) {
+        let event = Event {
+            kind,
+
+            inning: self.inning,
+            top: self.top,
+            batting_team: self.teams_batting().get_id(),
+            pitching_team: self.teams_pitching().get_id(),
+            pitcher: self.pitchers_pitching().id,
+            batter: self.batter.id,
+
+            count_before,
+            count_after: (self.balls, self.strikes),
+            outs: self.outs,
+            score_delta,
+        };
+        if let Some(message) = Message::from_event(&event, self) {
+            self.log(message);
+        }
+        self.statistics.record(&event);
+        self.events.push(event);
+    }
+
+    /// Runs one of the `Weather` hooks. The weather is taken out of the game
+    /// while the hook runs so it can change the game.
+    fn weather_hook(&mut self, hook: fn(&mut Weather, &mut Game)) {
+        let mut weather = std::mem::replace(&mut self.weather, Weather::Clear(RngBackend::default()));
+        hook(&mut weather, self);
+        self.weather = weather;
+    }
+
+    /// Looks up a player that's taking part in the game by their id.
+    pub fn find_player(&self, id: PlayerId) -> Option<&Player> {
+        [&self.batter, &self.defender, &self.home_pitcher, &self.away_pitcher]
+            .into_iter()
+            .chain(self.bases.iter().flatten())
+            .chain(self.home.players())
+            .chain(self.away.players())
+            .chain(self.incinerated.iter())
+            .find(|player| player.id == id)
+    }
+
+    /// Looks up one of the two teams playing by their id.
+    pub fn find_team(&self, id: TeamId) -> Option<&Team> {
+        self.teams().find(|team| team.get_id() == id)
+    }
+
+    /// Every modifier that applies to the player with the given id: their
+    /// own, plus the ones on the team they play for. Each kind only shows up
+    /// once, so having a modifier twice doesn't double its effect.
+    pub fn modifiers_of(&self, id: PlayerId) -> Vec<ModifierKind> {
+        // The team's copy is the one that's kept up to date
+        let player = self.teams()
+            .flat_map(Team::players)
+            .find(|player| player.id == id)
+            .or_else(|| self.find_player(id))
+            .into_iter()
+            .flat_map(Player::get_modifiers);
+        let team = self.teams()
+            .filter(|team| team.players().any(|player| player.id == id))
+            .flat_map(Team::get_modifiers);
+        let mut kinds = Vec::new();
+        for kind in player.chain(team).map(|modifier| modifier.kind) {
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+        kinds
+    }
+
+    /// Removes the modifiers that only last as long as `duration` from
+    /// both teams and every player taking part in the game.
+    fn expire_modifiers(&mut self, duration: ModifierDuration) {
+        for team in self.teams_mut() {
+            team.expire_modifiers(duration);
+        }
+        for player in self.slots_mut() {
+            player.expire_modifiers(duration);
+        }
+    }
+
+    // Everywhere a copy of a team's player is kept during the game
+    fn slots_mut(&mut self) -> impl Iterator<Item = &mut Player> {
+        [&mut self.home_pitcher, &mut self.away_pitcher, &mut self.batter, &mut self.defender]
+            .into_iter()
+            .chain(self.bases.iter_mut().flatten())
+    }
+
+    // Puts `replacement` in place of the player with the
+    // given id on their team and everywhere they are in the
+    // game. Returns the team's copy of the player it replaced.
+    fn replace_player(&mut self, id: PlayerId, replacement: Player) -> Option<Player> {
+        let removed = self.teams_mut()
+            .find_map(|team| team.replace_player(id, replacement.clone()))?;
+        for player in self.slots_mut() {
+            if player.id == id {
+                *player = replacement.clone();
+            }
+        }
+        Some(removed)
+    }
+
+    /// Incinerates the player with the given id and puts `replacement` in
+    /// their place, both on their team and wherever they are in the game
+    /// (on the mound, at bat, or on base). Returns false if the player isn't
+    /// playing in this game, or if one of their modifiers saved them.
+    pub fn incinerate(&mut self, victim: PlayerId, replacement: Player) -> bool {
+        let Some(mut survivor) = self.teams().flat_map(Team::players).find(|player| player.id == victim).cloned() else {
+            return false;
+        };
+        if self.modifiers_of(victim).iter().any(|modifier| modifier.resists_incineration(&mut survivor)) {
+            self.replace_player(victim, survivor);
+            self.emit(EventKind::FireEater { player: victim });
+            return false;
+        }
+
+        let Some(removed) = self.replace_player(victim, replacement.clone()) else {
+            return false;
+        };
+        self.incinerated.push(removed);
+        self.emit(EventKind::Incineration { victim, replacement: replacement.id });
+        true
     }
 
     // Okay here's the worst function in the code. I'm sorry.
     fn do_pitch(&mut self) {
-        let cur_pitcher = self.pitchers_pitching().clone();
+        let count = (self.balls, self.strikes);
         let pitch_value = self.rng.next_f64() * 10.0 + self.pitchers_pitching().clone().pinpointedness.value();
         let bat_value = self.rng.next_f64() * 10.0 + self.batter.density.value();
 
@@ -295,9 +562,9 @@ impl Game {
             if bat_value <= pitch_value {
                 self.strikes += 1;
                 if self.has_struck_out() {
-                    self.log(Message::StruckOutLooking(self.batter.get_name(), (self.balls, self.strikes)));
+                    self.emit_with_count(EventKind::StruckOutLooking, count);
                 } else {
-                    self.log(Message::StrikeLooking((self.balls, self.strikes)));
+                    self.emit_with_count(EventKind::StrikeLooking, count);
                 }
             } else {
                 let pitch_value = self.rng.next_f64() * 10.0 + self.pitchers_pitching().dimensions.value();
@@ -305,9 +572,9 @@ impl Game {
                 if bat_value <= pitch_value {
                     self.strikes += 1;
                     if self.has_struck_out() {
-                        self.log(Message::StruckOutSwinging(self.batter.get_name(), (self.balls, self.strikes)));
+                        self.emit_with_count(EventKind::StruckOutSwinging, count);
                     } else {
-                        self.log(Message::StrikeSwinging((self.balls, self.strikes)));
+                        self.emit_with_count(EventKind::StrikeSwinging, count);
                     }
                 }
             }
@@ -319,22 +586,23 @@ impl Game {
                     while self.has_struck_out() {
                         self.strikes -= 1;
                     }
-                    self.log(Message::FoulBall((self.balls, self.strikes)));
+                    self.emit_with_count(EventKind::FoulBall, count);
                 }
             } else {
                 let defender = self.get_random_defender();
+                self.defender = defender.clone();
                 let bat_value = self.rng.next_f64() * 10.0 + self.batter.aggression.value();
                 let defense_value = self.rng.next_f64() * 10.0 + defender.mathematics.value();
                 if bat_value <= defense_value {
                     // What the fuck?
                     self.strikes += 100;
-                    self.log(Message::Flyout(self.batter.get_name(), self.defender.get_name()))
+                    self.emit_with_count(EventKind::Flyout { defender: defender.id }, count);
                 } else {
                     let bat_value = self.rng.next_f64() * 10.0 + self.batter.hit_points.value();
                     let defense_value = self.rng.next_f64() * 10.0 + defender.damage.value();
                     if bat_value <= defense_value {
                         self.strikes += 100;
-                        self.log(Message::Groundout(self.batter.get_name(), self.defender.get_name()));
+                        self.emit_with_count(EventKind::Groundout { defender: defender.id }, count);
                     } else {
                         let mut bases_run = 0;
                         loop {
@@ -345,7 +613,8 @@ impl Game {
                                 break;
                             }
                         }
-                        self.log(Message::Hit(self.batter.get_name(), bases_run));
+                        // Anything past home is still just a home run
+                        self.emit_with_count(EventKind::Hit { bases: bases_run.min(self.rules.bases + 1) }, count);
                         self.advance_baserunners(bases_run);
                         self.set_next_batter();
                     }
@@ -361,25 +630,33 @@ impl Game {
 
         // You should not be accessing these values inside
         // of the function imo. They should be passed in.
-        let urge = self.rng.next_f64() * 10.0 + self.bases[base_num as usize].as_ref().unwrap().arrogance.value() - defender.rejection.value(); // I know using unwrap is prolly a bad idea here but this entire code is a bad idea
+        let urge = self.rng.next_f64() * 10.0 + self.bases[base_num ].as_ref().unwrap().arrogance.value() - defender.rejection.value(); // I know using unwrap is prolly a bad idea here but this entire code is a bad idea
         if urge < 9.9 {
             return;
         }
 
-        let steal_value = self.rng.next_f64() * 10.0 + self.bases[base_num as usize].clone().unwrap().dexterity.value();
+        let steal_value = self.rng.next_f64() * 10.0 + self.bases[base_num ].clone().unwrap().dexterity.value();
         let defense_value = self.rng.next_f64() * 10.0 + defender.wisdom.value();
+        let runner = self.bases[base_num].as_ref().unwrap().id;
 
-        if steal_value > defense_value {
+        let mut stolen = steal_value > defense_value;
+        if !stolen && self.modifiers_of(runner).iter().any(|modifier| modifier.escapes_tag(&mut self.rng)) {
+            self.emit(EventKind::Flicker { runner });
+            stolen = true;
+        }
+
+        if stolen {
             // The last part of the message will be
             // by the position of the base stolen
-            self.log(Message::Steal(self.bases[base_num as usize].clone().unwrap().get_name(), base_num));
-
-            // Holy shit why is this passing in an entire
-            // Player to a score function
-            self.score(&self.bases[base_num as usize].clone().unwrap());
-            // Why does this account for variable bases but
-            // other parts of your code doesn't?
-            if base_num <= self.bases.len() - 1 {
+            self.emit(EventKind::Steal { runner, base: base_num });
+
+            // Only stealing home scores. Everyone else just
+            // moves up a base.
+            if base_num == self.bases.len() - 1 {
+                // Holy shit why is this passing in an entire
+                // Player to a score function
+                self.score(&self.bases[base_num ].clone().unwrap());
+            } else {
                 // This feels bad for some reason
                 self.bases[base_num + 1] = self.bases[base_num].clone();
             }
@@ -389,10 +666,11 @@ impl Game {
         // It occurred to me just now that this is all a 
         // nested if statement.
         } else {
-            self.log(Message::CaughtStealing(self.bases[base_num].clone().unwrap().get_name(), base_num));
+            let count = (self.balls, self.strikes);
 
             // What the fuck? Why is this 100?
             self.strikes += 100;
+            self.emit_with_count(EventKind::CaughtStealing { runner, base: base_num }, count);
             self.bases[base_num] = None;
         }
         self.do_steals();
@@ -404,23 +682,34 @@ impl Game {
     //
     // Also the name for this function sucks.
     fn do_steals(&mut self) {
-        for (i, base) in self.bases.clone().iter_mut().enumerate() {
-            if let Some(player) = base {
-                if (i == self.bases.len() - 1 || self.bases[i + 1].is_none()) && !self.has_struck_out() {
-                    // Wait so this function passes in the
-                    // base number of the player that's
-                    // stealing instead of the player itself?
-                    self.steal_attempt(i);
-                }
+        // steal_attempt() calls back into this function, so
+        // the runners can move around under us. Always check
+        // the bases as they are right now.
+        for i in 0..self.bases.len() {
+            if self.can_steal(i) {
+                // Wait so this function passes in the
+                // base number of the player that's
+                // stealing instead of the player itself?
+                self.steal_attempt(i);
             }
         }
     }
 
+    /// Whether the runner on `base_num` can try to steal: there's
+    /// someone there, the next base is free, and the at-bat isn't
+    /// already over.
+    pub fn can_steal(&self, base_num: usize) -> bool {
+        self.bases[base_num].is_some()
+            && (base_num == self.bases.len() - 1 || self.bases[base_num + 1].is_none())
+            && !self.has_struck_out()
+    }
+
     // Incredibly cursed function.
     fn advance_baserunners(&mut self, base_num: usize) {
         let mut scored = false;
         let mut new_bases: Vec<Option<Player>> = self.bases.clone();
         let mut score_queue: Vec<Player> = Vec::new();
+        let mut advance_queue: Vec<(PlayerId, usize, usize)> = Vec::new();
         for (i, base) in self.bases.iter_mut().enumerate().rev() {
             if let Some(player) = base {
                 if i + base_num >= new_bases.len() {
@@ -429,12 +718,16 @@ impl Game {
                     new_bases[i] = None;
                     scored = true;
                 } else {
+                    advance_queue.push((player.id, i, i + base_num));
                     new_bases[i + base_num] = new_bases[i].clone();
                     new_bases[i] = None;
                 }
             }
         }
         self.bases = new_bases;
+        for (runner, from, to) in advance_queue {
+            self.emit(EventKind::Advance { runner, from, to });
+        }
         if scored {
             for player in score_queue {
                 self.score(&player);
@@ -454,13 +747,18 @@ impl Game {
         true
     }
 
+    // Scores are stored as (home, away). The away team bats
+    // at the top of the inning.
     fn score(&mut self, p: &Player) {
-        if self.top {
-            self.scores.0 += 1.0;
+        let value = self.modifiers_of(p.id).iter().fold(1.0, |value, modifier| modifier.run_value(value));
+        let score_delta = if self.top {
+            (0.0, value)
         } else {
-            self.scores.1 += 1.0;
-        }
-        self.log_with_time(Message::Scores(p.get_name()), 0);
+            (value, 0.0)
+        };
+        self.scores.0 += score_delta.0;
+        self.scores.1 += score_delta.1;
+        self.emit_full_event(EventKind::Score { runner: p.id }, (self.balls, self.strikes), score_delta);
     }
 
     // Unused code
@@ -475,7 +773,7 @@ impl Game {
     pub fn get_game_name(&self) -> String {
         format!(
             "{}, {} vs. {}, Day {}", 
-            self.home.get_name(), // TODO: Make this the current weather once that is implemented
+            self.weather.name(),
             self.away.get_name(),
             self.home.get_name(), 
             self.day
@@ -483,8 +781,9 @@ impl Game {
     }
 
     // Precondition: simulate_game() has been called already
-    pub fn is_live(&self) -> bool {
-        self.current_time >= SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() && self.start_time <= SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis()
+    pub fn is_live(&self, clock: &dyn Clock) -> bool {
+        let now = clock.now();
+        self.current_time >= now && self.start_time <= now
     }
 
     fn get_random_defender(&mut self) -> Player {
@@ -495,72 +794,60 @@ impl Game {
     }
 
     /// Returns true if the batter has struck out.
-    /// 
-    /// NOTE: This will need to be updated to be variable
-    /// in the future if features like fourth strike are
-    /// added
     fn has_struck_out(&self) -> bool {
-        self.strikes >= 3
+        self.strikes >= self.strikes_needed()
     }
 
-    // Idk how to add this to the game log :/
-    fn format_balls_strikes(&self) -> String {
-        let mut b = self.balls.to_string();
-        let mut s = self.strikes.to_string();
-
-        if self.balls < 0 {
-            b = format!("(-{})", self.balls.abs());
-        }
-        if self.strikes < 0 {
-            s = format!("(-{})", self.strikes.abs());
-        }
-        return format!("{}-{}", b, s);
+    // How many strikes or balls the batter at the plate
+    // needs, after their modifiers have had their say.
+    fn strikes_needed(&self) -> i32 {
+        self.modifiers_of(self.batter.id).iter().fold(self.rules.strikes, |strikes, modifier| modifier.strikes(strikes))
+    }
+    fn balls_needed(&self) -> i32 {
+        self.modifiers_of(self.batter.id).iter().fold(self.rules.balls, |balls, modifier| modifier.balls(balls))
     }
 
     fn clear_bases(&mut self) {
-        self.bases = vec![None, None, None];
+        self.bases = vec![None; self.rules.bases];
     }
 
     fn ball(&mut self) {
         self.balls += 1;
-        if self.balls >= 4 {
+        if self.balls >= self.balls_needed() {
             // For some reason in the original code, the
             // Player class has a get_name() function but
             // it's never used. Mixed just grabs the name
             // from the variables.
-            self.log(Message::Walk(self.batter.get_name()));
+            self.emit_with_count(EventKind::Walk, (self.balls - 1, self.strikes));
             self.walk();
             self.set_next_batter();
         } else {
-            self.log(Message::Ball((self.balls, self.strikes)))
+            self.emit_with_count(EventKind::Ball, (self.balls - 1, self.strikes));
+            self.weather_hook(Weather::after_ball);
         }
     }
 
+    // Forces every runner ahead of the batter up a base,
+    // stopping at the first empty one.
     fn walk(&mut self) {
-        let mut scored = false;
-        // This feels like the borrow checker will hate it
-        let mut moving = self.batter.clone();
-        let mut new_bases = self.bases.clone();
-        let mut score_queue: Vec<Player> = Vec::new();
-        for (i, base) in self.bases.iter_mut().enumerate() {
-            if let Some(player) = base {
-                // Swap the moving player with the player on base
-                new_bases[i] = Some(moving.clone());
-                moving = player.clone();
-                if i == new_bases.len() - 1 {
-                    // self.score(&moving); // Breaks borrow checker
-                    score_queue.push(moving.clone());
-                    scored = true;
+        let mut moving = Some(self.batter.clone());
+        let mut advance_queue: Vec<(PlayerId, usize, usize)> = Vec::new();
+        for i in 0..self.bases.len() {
+            if let Some(player) = moving {
+                if player != self.batter {
+                    advance_queue.push((player.id, i - 1, i));
                 }
+                moving = self.bases[i].replace(player);
             } else {
-                self.bases[i] = Some(moving);
                 break;
             }
         }
-        if scored {
-            for player in score_queue {
-                self.score(&player);
-            }
+        for (runner, from, to) in advance_queue {
+            self.emit(EventKind::Advance { runner, from, to });
+        }
+        // Whoever's still moving was pushed off the last base
+        if let Some(player) = moving {
+            self.score(&player);
             self.print_score();
         }
     }
@@ -569,6 +856,12 @@ impl Game {
         self.log_with_time(Message::CurrentScore(self.home.abbreviation.clone(), self.scores.0, self.scores.1, self.away.abbreviation.clone()), 0);
     }
 
+    /// Builds the box score for the game. Usually called once it's over,
+    /// but works at any point along the way.
+    pub fn box_score(&self) -> BoxScore {
+        BoxScore::new(self)
+    }
+
     /// Retrieves the teams from the Game struct. The use
     /// is for updating the real Teams which is stored
     /// outside of the Game.
@@ -581,19 +874,35 @@ impl Game {
         if self.scores.0 > self.scores.1 {
             self.wins.0 += 1;
             if self.day < 100 {
-                &mut self.home.add_win();
-                &mut self.away.add_loss();
+                self.home.add_non_loss();
+                self.away.add_loss();
             }
         } else {
             self.wins.1 += 1;
             if self.day < 100 {
-                &mut self.away.add_win();
-                &mut self.home.add_loss();
+                self.away.add_non_loss();
+                self.home.add_loss();
             }
         }
+        // The winner's actual win is counted above. This is
+        // where the wins shown in the standings come from.
         if self.day < 100 {
-            &mut self.home.add_win_by(self.wins.0);
-            &mut self.away.add_win_by(self.wins.1);
+            self.home.add_win_by(self.wins.0);
+            self.away.add_win_by(self.wins.1);
+        }
+    }
+
+    // Adds this game's stats to everyone's season stats,
+    // including anyone that got incinerated along the way.
+    fn give_statistics(&mut self) {
+        let statistics = &self.statistics;
+        let players = self.home.players_mut()
+            .chain(self.away.players_mut())
+            .chain(self.incinerated.iter_mut());
+        for player in players {
+            if let Some(game_statistics) = statistics.get(player.id) {
+                player.add_statistics(game_statistics);
+            }
         }
     }
 
@@ -605,21 +914,43 @@ impl Game {
         }
         self.strikes = 0;
         self.balls = 0;
-        
-        *self.bat_batting_mut()+= 1;
+
+        // Only someone who just batted for this team can bat again
+        let batter = self.batter.id;
+        let batted = self.teams_batting().active_players().any(|player| player.id == batter);
+        if batted && self.modifiers_of(batter).iter().any(|modifier| modifier.bats_again(&mut self.rng)) {
+            self.emit(EventKind::Reverberating { player: batter });
+            self.emit(EventKind::NextBatter);
+            return;
+        }
+
+        // Skip over anyone who can't play, unless nobody can
         let batting_team = self.teams_batting().get_active_batters();
-        self.batter = batting_team[(self.bat_batting() - 1) % batting_team.len()].clone();
+        for _ in 0..batting_team.len() {
+            *self.bat_batting_mut()+= 1;
+            self.batter = batting_team[(self.bat_batting() - 1) % batting_team.len()].clone();
+            if self.modifiers_of(self.batter.id).iter().all(ModifierKind::can_play) {
+                break;
+            }
+        }
 
-        self.log(Message::NextBatter(self.batter.get_name(), self.teams_batting().get_name()));
+        self.emit(EventKind::NextBatter);
     }
+}
 
-    pub fn play_logs(&mut self) {
-        for i in 0..self.message_log.len().unwrap() {
-            let log = self.message_log.pop_front().unwrap();
-            while SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() < log.1 {
-                thread::sleep(Duration::from_millis(TICK.try_into().unwrap()));
-            }
-            println!("{}", log.0.message_line());
+/// Steps through a `Game` one phase at a time. Created by `Game::steps()`.
+pub struct Steps<'a> {
+    game: &'a mut Game,
+}
+
+impl<'a> Iterator for Steps<'a> {
+    type Item = Vec<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.game.is_over() {
+            None
+        } else {
+            Some(self.game.step())
         }
     }
 }
@@ -646,4 +977,302 @@ pub fn score_as_string(score: f64) -> String {
         message = format!("({})", message);
     }
     message
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_incinerate_replaces_player_everywhere() {
+    use crate::java_random::Random;
+
+    let mut rng = Random::new(4);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules::default());
+    game.step(); // Pregame picks the pitchers
+
+    let victim = game.home_pitcher.clone();
+    let replacement = Player::default(&mut rng);
+    assert!(game.incinerate(victim.id, replacement.clone()));
+
+    assert_eq!(game.home_pitcher, replacement);
+    assert!(game.home.players().any(|player| *player == replacement));
+    assert!(!game.home.players().any(|player| *player == victim));
+    assert_eq!(game.incinerated, vec![victim.clone()]);
+    assert_eq!(game.events.last().unwrap().kind, EventKind::Incineration { victim: victim.id, replacement: replacement.id });
+
+    // They're already gone
+    assert!(!game.incinerate(victim.id, Player::default(&mut rng)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip_resumes_identically() {
+    use crate::java_random::Random;
+
+    let mut rng = Random::new(7);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 2, 0, Rules::default());
+    for _ in 0..150 {
+        game.step();
+    }
+
+    let saved = serde_json::to_string(&game).unwrap();
+    let mut loaded: Game = serde_json::from_str(&saved).unwrap();
+    assert_eq!(serde_json::to_string(&loaded).unwrap(), saved);
+
+    game.simulate_game();
+    loaded.simulate_game();
+    assert_eq!(loaded.events, game.events);
+    assert_eq!(loaded.scores, game.scores);
+    assert_eq!(loaded.message_log.messages, game.message_log.messages);
+}
+
+#[test]
+fn test_custom_rules() {
+    use crate::java_random::Random;
+
+    let rules = Rules { strikes: 4, balls: 5, outs: 2, innings: 3, bases: 4 };
+    let mut rng = Random::new(12);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, rules);
+    assert_eq!(game.bases.len(), 4);
+
+    let mut lines = Vec::new();
+    while !game.is_over() {
+        game.step();
+        assert!(game.outs <= rules.outs);
+        assert!(game.balls < rules.balls);
+        assert_eq!(game.bases.len(), 4);
+        while let Some((message, _)) = game.message_log.pop_front() {
+            lines.push(message.message_line());
+        }
+    }
+    assert!(game.inning > rules.innings);
+    assert!(lines.iter().any(|line| line.contains(" strikes out ") && line.ends_with("-4")));
+    assert!(game.events.iter().all(|event| !matches!(event.kind, EventKind::Hit { bases } if bases > 5)));
+    assert_eq!(Message::Steal("Nepe Oobocu".to_string(), Some(4)).message_line(), "Nepe Oobocu steals fourth base!");
+    assert_eq!(Message::Hit("Nepe Oobocu".to_string(), Some(4)).message_line(), "Nepe Oobocu hits a Quadruple!");
+}
+
+#[test]
+fn test_modifiers() {
+    use crate::java_random::Random;
+    use crate::modifiers::Modifier;
+
+    // Fourth Strike on the whole away team, and one of the home team's batters shelled
+    let mut rng = Random::new(3);
+    let mut home = Team::empty(&mut rng);
+    let mut away = Team::empty(&mut rng);
+    away.add_modifier(Modifier::permanent(ModifierKind::FourthStrike));
+    let shelled = home.get_active_batters()[0].id;
+    for player in home.players_mut().filter(|player| player.id == shelled) {
+        player.add_modifier(Modifier::permanent(ModifierKind::Shelled));
+    }
+    let away_id = away.get_id();
+
+    let mut game = Game::new(home, away, 1, 0, Rules::default());
+    game.simulate_game();
+    let strikeouts: Vec<&Event> = game.events.iter()
+        .filter(|event| matches!(event.kind, EventKind::StruckOutLooking | EventKind::StruckOutSwinging))
+        .collect();
+    // Outs on balls in play add 100 strikes, so only look at regular strikeouts
+    let strikes = |away: bool| -> Vec<i32> {
+        strikeouts.iter()
+            .filter(|event| (event.batting_team == away_id) == away && event.count_after.1 < 100)
+            .map(|event| event.count_after.1)
+            .collect()
+    };
+    assert!(strikes(true).contains(&4) && !strikes(true).contains(&3));
+    assert!(strikes(false).contains(&3) && !strikes(false).contains(&4));
+    assert!(!game.events.iter().any(|event| event.kind == EventKind::NextBatter && event.batter == shelled));
+}
+
+#[test]
+fn test_fire_eater_and_expiry() {
+    use crate::java_random::Random;
+    use crate::modifiers::Modifier;
+
+    let mut rng = Random::new(4);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules::default());
+    game.step();
+
+    let pitcher = game.home_pitcher.clone();
+    game.home.players_mut().find(|player| player.id == pitcher.id).unwrap()
+        .add_modifier(Modifier::new(ModifierKind::FireEater, ModifierDuration::Inning));
+    assert!(!game.incinerate(pitcher.id, Player::default(&mut rng)));
+    assert_eq!(game.events.last().unwrap().kind, EventKind::FireEater { player: pitcher.id });
+    assert!(game.incinerated.is_empty());
+    assert!(game.home_pitcher.get_batting() > pitcher.get_batting());
+    assert!(game.home_pitcher.has_modifier(ModifierKind::FireEater));
+
+    // It only lasts the inning
+    while game.phase != Phase::HalfInningEnd {
+        game.step();
+    }
+    game.step();
+    assert!(game.modifiers_of(pitcher.id).is_empty());
+    assert!(game.incinerate(pitcher.id, Player::default(&mut rng)));
+}
+
+#[test]
+fn test_runs_go_to_the_batting_team() {
+    use crate::java_random::Random;
+
+    let mut rng = Random::new(5);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules::default());
+    game.step();
+
+    // The away team bats at the top of the inning, and scores are (home, away)
+    let runner = Player::default(&mut rng);
+    game.top = true;
+    game.bases[2] = Some(runner.clone());
+    game.advance_baserunners(1);
+    assert_eq!(game.scores, (0.0, 1.0));
+    let scored = game.events.iter().rev().find(|event| event.kind == EventKind::Score { runner: runner.id }).unwrap();
+    assert_eq!(scored.score_delta, (0.0, 1.0));
+    assert_eq!(scored.batting_team, game.away.get_id());
+
+    game.top = false;
+    game.bases[2] = Some(runner);
+    game.advance_baserunners(1);
+    assert_eq!(game.scores, (1.0, 1.0));
+}
+
+#[test]
+fn test_walks_force_runners_up_one_base() {
+    use crate::java_random::Random;
+
+    let mut rng = Random::new(6);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules::default());
+    game.step();
+    let ids = |game: &Game| -> Vec<Option<PlayerId>> { game.bases.iter().map(|base| base.as_ref().map(|player| player.id)).collect() };
+
+    // Only the runner on first is forced, the one on third stays put
+    let first = Player::default(&mut rng);
+    let third = Player::default(&mut rng);
+    game.bases = vec![Some(first.clone()), None, Some(third.clone())];
+    let batter = game.batter.id;
+    game.walk();
+    assert_eq!(ids(&game), vec![Some(batter), Some(first.id), Some(third.id)]);
+    assert_eq!(game.scores, (0.0, 0.0));
+
+    // With the bases loaded, the runner on third is walked home
+    game.batter = Player::default(&mut rng);
+    game.walk();
+    assert_eq!(ids(&game), vec![Some(game.batter.id), Some(batter), Some(first.id)]);
+    assert_eq!(game.scores, (0.0, 1.0));
+    assert!(game.events.iter().any(|event| event.kind == EventKind::Score { runner: third.id }));
+}
+
+#[test]
+fn test_outs_in_play_name_the_defender() {
+    use crate::java_random::Random;
+
+    let mut rng = Random::new(8);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules::default());
+    game.simulate_game();
+
+    let mut outs_in_play = 0;
+    for event in game.events.iter() {
+        let name = |id: PlayerId| game.find_player(id).unwrap().get_name();
+        let (defender, expected) = match event.kind {
+            EventKind::Flyout { defender } => (defender, Message::Flyout(name(event.batter), name(defender))),
+            EventKind::Groundout { defender } => (defender, Message::Groundout(name(event.batter), name(defender))),
+            _ => continue,
+        };
+        let fielding = game.find_team(event.pitching_team).unwrap();
+        assert!(fielding.get_active_batters().iter().any(|player| player.id == defender));
+        assert_eq!(Message::from_event(event, &game), Some(expected));
+        outs_in_play += 1;
+    }
+    assert!(outs_in_play > 0);
+}
+
+#[test]
+fn test_stepping_plays_the_same_game() {
+    use crate::java_random::Random;
+
+    let mut rng = Random::new(9);
+    let home = Team::empty(&mut rng);
+    let away = Team::empty(&mut rng);
+    let mut simulated = Game::new(home.clone(), away.clone(), 3, 0, Rules::default());
+    simulated.simulate_game();
+
+    // One phase at a time
+    let mut stepped = Game::new(home.clone(), away.clone(), 3, 0, Rules::default());
+    let mut events = Vec::new();
+    let mut phases = 0;
+    while !stepped.is_over() {
+        events.extend(stepped.step());
+        phases += 1;
+    }
+    assert!(phases > 1);
+    assert_eq!(events, simulated.events);
+    assert_eq!(stepped.scores, simulated.scores);
+    assert_eq!(stepped.message_log.messages, simulated.message_log.messages);
+
+    // Nothing more happens once it's over
+    assert_eq!(stepped.phase, Phase::Over);
+    assert!(stepped.step().is_empty());
+    assert_eq!(stepped.events.len(), simulated.events.len());
+
+    let mut iterated = Game::new(home, away, 3, 0, Rules::default());
+    let events: Vec<Event> = iterated.steps().flatten().collect();
+    assert_eq!(events, simulated.events);
+    assert!(iterated.is_over());
+    assert_eq!(iterated.steps().next(), None);
+}
+
+#[test]
+fn test_only_stealing_home_scores() {
+    use crate::java_random::Random;
+    use crate::player_stats::CharacterStat;
+
+    let mut rng = Random::new(10);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules::default());
+    game.step();
+
+    // Nobody can stop them, so they steal every base on the way home
+    let mut runner = Player::default(&mut rng);
+    runner.arrogance = CharacterStat::new(100.0);
+    runner.dexterity = CharacterStat::new(100.0);
+    game.bases[0] = Some(runner.clone());
+    game.steal_attempt(0);
+
+    let steals: Vec<usize> = game.events.iter()
+        .filter_map(|event| match event.kind {
+            EventKind::Steal { runner: id, base } if id == runner.id => Some(base),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(steals, vec![0, 1, 2]);
+    assert_eq!(game.scores, (0.0, 1.0));
+    assert!(game.are_bases_empty());
+}
+
+#[test]
+fn test_stacked_modifiers_count_once() {
+    use crate::java_random::Random;
+    use crate::modifiers::Modifier;
+
+    // Fourth Strike on both the player and their team is still only four strikes
+    let mut rng = Random::new(3);
+    let home = Team::empty(&mut rng);
+    let mut away = Team::empty(&mut rng);
+    away.add_modifier(Modifier::permanent(ModifierKind::FourthStrike));
+    let batter = away.get_active_batters()[0].id;
+    for player in away.players_mut().filter(|player| player.id == batter) {
+        player.add_modifier(Modifier::permanent(ModifierKind::FourthStrike));
+        player.add_modifier(Modifier::new(ModifierKind::FourthStrike, ModifierDuration::Game));
+    }
+
+    let mut game = Game::new(home, away, 1, 0, Rules::default());
+    game.step();
+    assert_eq!(game.modifiers_of(batter), vec![ModifierKind::FourthStrike]);
+    game.batter = game.find_player(batter).unwrap().clone();
+    assert_eq!(game.strikes_needed(), 4);
+}
+
+#[test]
+#[should_panic(expected = "outs has to be at least 1")]
+fn test_games_refuse_unplayable_rules() {
+    use crate::java_random::Random;
+
+    let mut rng = Random::new(14);
+    Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules { outs: 0, ..Rules::default() });
+}
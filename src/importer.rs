@@ -0,0 +1,357 @@
+//! Imports rosters and standings saved by the Java version of Alt League Blaseball.
+//!
+//! Every save file is a Java `.properties` file, as written by
+//! `java.util.Properties::store`: one `key=value` per line, `#` and `!`
+//! comments, `\uXXXX` escapes for anything outside of Latin-1 (so emoji logos
+//! show up as surrogate pairs), and a trailing `\` to continue a value on the
+//! next line.
+//!
+//! A league save is a directory laid out like this:
+//!
+//! ```text
+//! league.properties               teams.0, teams.1, ... (team names)
+//! teams/<Team Name>.properties
+//! players/<Player Name>.properties
+//! ```
+//!
+//! Team files have `name`, `location`, `logo`, `abbreviation`, `favor`,
+//! `wins`, `losses` and `actualWins`, followed by `lineup.0`, `lineup.1`, ...
+//! and `rotation.0`, `rotation.1`, ... naming the players on the team.
+//!
+//! Player files have `name`, all 20 stats (spelled like `Player::STAT_NAMES`),
+//! `pregameRitual`, `coffee`, `bloodType`, `fate` and `soulscream`. Players
+//! saved before soulscreams existed get theirs generated from their name,
+//! the same way the Java game does it.
+//!
+//! Players and teams don't have ids in the Java game. Players get new ones
+//! drawn from the RNG passed in, and each team's id is worked out from its
+//! players, see `Team::with_players()`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::rng::GameRng;
+use crate::league::{League, LeagueError, Players};
+use crate::player::Player;
+use crate::player_flavor::{BloodType, CoffeeStyle, Fate, PregameRitual, Soulscream};
+use crate::player_stats::CharacterStat;
+use crate::team::Team;
+
+#[derive(Debug)]
+pub enum ImportError {
+  Io(PathBuf, io::Error), // File that couldn't be read
+  Syntax { line: usize, message: String },
+  MissingField(String),
+  InvalidValue { field: String, value: String },
+  UnknownPlayer(String), // Player Name
+  League(LeagueError),
+}
+
+impl fmt::Display for ImportError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ImportError::Io(path, error) => write!(f, "couldn't read {}: {}", path.display(), error),
+      ImportError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+      ImportError::MissingField(field) => write!(f, "missing field `{}`", field),
+      ImportError::InvalidValue { field, value } => write!(f, "invalid value for `{}`: {:?}", field, value),
+      ImportError::UnknownPlayer(name) => write!(f, "no player named {:?}", name),
+      ImportError::League(error) => write!(f, "{}", error),
+    }
+  }
+}
+
+impl std::error::Error for ImportError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      ImportError::Io(_, error) => Some(error),
+      ImportError::League(error) => Some(error),
+      _ => None,
+    }
+  }
+}
+
+/// Reads a player out of a player save file.
+pub fn import_player(text: &str, rng: &mut impl GameRng) -> Result<Player, ImportError> {
+  let properties = Properties::from_text(text)?;
+  let name = properties.get("name")?.to_string();
+
+  // Everything this rolls gets overwritten. It's just where the id comes from.
+  let mut player = Player::new(rng, name.clone());
+  for (stat, stat_name) in player.stats_mut().into_iter().zip(Player::STAT_NAMES) {
+    *stat = CharacterStat::new(properties.parse(stat_name)?);
+  }
+
+  player.set_ritual(PregameRitual::new_from_str(properties.get("pregameRitual")?.to_string()));
+  player.set_coffee(properties.lookup("coffee", CoffeeStyle::from_name)?);
+  player.set_blood_type(properties.lookup("bloodType", BloodType::from_name)?);
+  player.set_fate(Fate::new(properties.parse("fate")?));
+  let soulscream = match properties.get("soulscream") {
+    Ok(soulscream) => Soulscream::new_from_str(soulscream.to_string()),
+    Err(_) => Soulscream::generate_soulscream(name),
+  };
+  player.set_soulscream(soulscream);
+
+  Ok(player)
+}
+
+/// Reads a team and its standings out of a team save file. The players it
+/// names are looked up in `players`.
+pub fn import_team(text: &str, players: &Players) -> Result<Team, ImportError> {
+  let properties = Properties::from_text(text)?;
+
+  let find = |name: &str| {
+    players
+      .find_by_name(name)
+      .cloned()
+      .ok_or_else(|| ImportError::UnknownPlayer(name.to_string()))
+  };
+  let lineup = properties.list("lineup").into_iter().map(find).collect::<Result<Vec<Player>, ImportError>>()?;
+  let rotation = properties.list("rotation").into_iter().map(find).collect::<Result<Vec<Player>, ImportError>>()?;
+  if lineup.is_empty() {
+    return Err(ImportError::MissingField("lineup.0".to_string()));
+  }
+  if rotation.is_empty() {
+    return Err(ImportError::MissingField("rotation.0".to_string()));
+  }
+
+  let mut team = Team::with_players(
+    properties.get("name")?.to_string(),
+    properties.get("location")?.to_string(),
+    properties.get("logo")?.to_string(),
+    properties.parse("favor")?,
+    properties.get("abbreviation")?.to_string(),
+    lineup,
+    rotation,
+  );
+  team.set_record(properties.parse("wins")?, properties.parse("losses")?, properties.parse("actualWins")?);
+
+  Ok(team)
+}
+
+/// Loads a whole league save directory: every team listed in
+/// `league.properties`, along with their players and standings.
+pub fn load_league(dir: &Path, rng: &mut impl GameRng) -> Result<League, ImportError> {
+  let league_properties = Properties::from_text(&read(&dir.join("league.properties"))?)?;
+
+  let mut league = League::empty();
+  for team_name in league_properties.list("teams") {
+    let team_text = read(&dir.join("teams").join(format!("{}.properties", team_name)))?;
+    let team_properties = Properties::from_text(&team_text)?;
+
+    let mut players = Players::empty();
+    for player_name in team_properties.list("lineup").into_iter().chain(team_properties.list("rotation")) {
+      let player_text = read(&dir.join("players").join(format!("{}.properties", player_name)))?;
+      players.insert(import_player(&player_text, rng)?);
+    }
+
+    league.add_team(import_team(&team_text, &players)?).map_err(ImportError::League)?;
+  }
+  Ok(league)
+}
+
+pub(crate) fn read(path: &Path) -> Result<String, ImportError> {
+  fs::read_to_string(path).map_err(|error| ImportError::Io(path.to_path_buf(), error))
+}
+
+/// The keys and values of a `.properties` file.
+pub(crate) struct Properties {
+  values: BTreeMap<String, String>,
+}
+
+impl Properties {
+  pub(crate) fn from_text(text: &str) -> Result<Self, ImportError> {
+    let mut values = BTreeMap::new();
+    let mut lines = text.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+      let mut logical = line.trim_start().to_string();
+      if logical.is_empty() || logical.starts_with('#') || logical.starts_with('!') {
+        continue;
+      }
+      while ends_in_continuation(&logical) {
+        logical.pop();
+        match lines.next() {
+          Some((_, next)) => logical.push_str(next.trim_start()),
+          None => break,
+        }
+      }
+
+      let (key, value) = split_key_value(&logical);
+      values.insert(unescape(key, index + 1)?, unescape(value, index + 1)?);
+    }
+    Ok(Properties {
+      values,
+    })
+  }
+
+  pub(crate) fn get(&self, field: &str) -> Result<&str, ImportError> {
+    self.values
+      .get(field)
+      .map(|value| value.as_str())
+      .ok_or_else(|| ImportError::MissingField(field.to_string()))
+  }
+
+  pub(crate) fn parse<T: FromStr>(&self, field: &str) -> Result<T, ImportError> {
+    self.lookup(field, |value| value.trim().parse().ok())
+  }
+
+  pub(crate) fn lookup<T>(&self, field: &str, find: impl Fn(&str) -> Option<T>) -> Result<T, ImportError> {
+    let value = self.get(field)?;
+    find(value).ok_or_else(|| ImportError::InvalidValue {
+      field: field.to_string(),
+      value: value.to_string(),
+    })
+  }
+
+  /// Reads `prefix.0`, `prefix.1`, ... until one of them is missing.
+  pub(crate) fn list(&self, prefix: &str) -> Vec<&str> {
+    (0..)
+      .map_while(|index| self.values.get(&format!("{}.{}", prefix, index)))
+      .map(|value| value.as_str())
+      .collect()
+  }
+}
+
+// A line is continued if it ends in an odd number of backslashes.
+fn ends_in_continuation(line: &str) -> bool {
+  line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+// The key ends at the first unescaped '=', ':' or whitespace. Whitespace
+// around the separator isn't part of either side.
+fn split_key_value(line: &str) -> (&str, &str) {
+  let mut escaped = false;
+  let mut key_end = line.len();
+  for (i, c) in line.char_indices() {
+    if escaped {
+      escaped = false;
+    } else if c == '\\' {
+      escaped = true;
+    } else if c == '=' || c == ':' || c.is_whitespace() {
+      key_end = i;
+      break;
+    }
+  }
+
+  let rest = line[key_end..].trim_start();
+  let rest = rest.strip_prefix(['=', ':']).unwrap_or(rest).trim_start();
+  (&line[..key_end], rest)
+}
+
+fn unescape(text: &str, line: usize) -> Result<String, ImportError> {
+  // \u escapes are UTF-16 code units, so everything gets decoded as UTF-16
+  // to put surrogate pairs back together.
+  let mut units: Vec<u16> = Vec::new();
+  let mut chars = text.chars();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      let mut buffer = [0; 2];
+      units.extend_from_slice(c.encode_utf16(&mut buffer));
+      continue;
+    }
+    match chars.next() {
+      Some('t') => units.push('\t' as u16),
+      Some('n') => units.push('\n' as u16),
+      Some('r') => units.push('\r' as u16),
+      Some('f') => units.push(0x0C),
+      Some('u') => {
+        let hex: String = chars.by_ref().take(4).collect();
+        let unit = u16::from_str_radix(&hex, 16).map_err(|_| ImportError::Syntax {
+          line,
+          message: format!("malformed \\u escape: \\u{}", hex),
+        })?;
+        units.push(unit);
+      },
+      Some(other) => {
+        let mut buffer = [0; 2];
+        units.extend_from_slice(other.encode_utf16(&mut buffer));
+      },
+      None => {},
+    }
+  }
+  String::from_utf16(&units).map_err(|_| ImportError::Syntax {
+    line,
+    message: "unpaired surrogate".to_string(),
+  })
+}
+
+#[cfg(test)]
+const PLAYER_SAVE: &str = "#Alt League Blaseball player
+#Sat Jan 01 00:00:00 UTC 2022
+name=Wyatt Mason
+aggression=1.5
+arrogance=2.25
+carcinization=0.5
+damage=3.0
+density=4.75
+dexterity=1.0
+dimensions=2.0
+effort=3.5
+focus=0.25
+fun=4.0
+grit=2.5
+hitPoints=1.25
+malleability=3.75
+mathematics=0.75
+numberOfEyes=4.5
+pinpointedness=2.75
+powder=1.75
+rejection=3.25
+splash=0.0
+wisdom=5.0
+pregameRitual=Eating multiple birds
+coffee=Cream & Sugar
+bloodType=O No
+fate=42
+soulscream=AAAAAHHHHXXX\\
+  OOOO
+";
+
+#[test]
+fn test_import_player() {
+  use crate::java_random::Random;
+
+  let mut rng = Random::new(0);
+  let player = import_player(PLAYER_SAVE, &mut rng).unwrap();
+
+  assert_eq!(player.name, "Wyatt Mason");
+  assert_eq!(player.aggression.value(), 1.5);
+  assert_eq!(player.hit_points.value(), 1.25);
+  assert_eq!(player.number_of_eyes.value(), 4.5);
+  assert_eq!(player.wisdom.value(), 5.0);
+  assert_eq!(player.get_ritual().as_str(), "Eating multiple birds");
+  assert_eq!(player.get_coffee().as_str(), "Cream & Sugar");
+  assert_eq!(player.get_blood_type().as_str(), "O No");
+  assert_eq!(player.get_fate().as_u8(), 42);
+  assert_eq!(player.get_soulscream().as_str(), "AAAAAHHHHXXXOOOO");
+}
+
+#[test]
+fn test_import_team() {
+  use crate::java_random::Random;
+
+  let mut rng = Random::new(0);
+  let mut players = Players::empty();
+  players.insert(import_player(PLAYER_SAVE, &mut rng).unwrap());
+  let mut pitcher = Player::default(&mut rng);
+  pitcher.name = "Pitching Machine".to_string();
+  players.insert(pitcher);
+
+  let save = "name=Crabs\nlocation=Baltimore\nlogo=\\uD83E\\uDD80\nabbreviation=BAL\nfavor=3\nwins=12\nlosses=5\nactualWins=10\nlineup.0=Wyatt Mason\nrotation.0=Pitching Machine\n";
+  let team = import_team(save, &players).unwrap();
+
+  assert_eq!(team.get_team_name(), "Crabs Baltimore");
+  assert_eq!(team.logo, "🦀");
+  assert_eq!(team.get_favor(), 3);
+  assert_eq!(team.get_wins(), 12);
+  assert_eq!(team.get_losses(), 5);
+  assert_eq!(team.get_non_losses(), 10);
+  assert_eq!(team.get_active_batters()[0].name, "Wyatt Mason");
+  assert_eq!(team.get_active_pitchers()[0].name, "Pitching Machine");
+
+  let missing = save.replace("rotation.0=Pitching Machine", "rotation.0=Nobody");
+  assert!(matches!(import_team(&missing, &players), Err(ImportError::UnknownPlayer(name)) if name == "Nobody"));
+}
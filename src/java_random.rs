@@ -4,6 +4,7 @@
 // source code that I could find, But it doesn't compile.
 // So I'm just going to use what I need.
 
+use std::cmp::Ordering;
 use std::num::Wrapping;
 
 pub const MULTIPLIER: Wrapping<i64> = Wrapping(0x5DEECE66D);
@@ -14,8 +15,12 @@ const F32_DIV: f32 = (1u32 << 24) as f32;
 const F64_DIV: f64 = (1u64 << 53) as f64;
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Random {
     state: Wrapping<i64>,
+    // The second of the pair of Gaussians `next_gaussian` makes, for the next call
+    #[cfg_attr(feature = "serde", serde(default))]
+    next_next_gaussian: Option<f64>,
 }
 
 impl Random {
@@ -25,6 +30,16 @@ impl Random {
     pub fn new(seed: i64) -> Self {
         Random {
             state: Wrapping((seed) ^ MULTIPLIER.0) & MASK,
+            next_next_gaussian: None,
+        }
+    }
+
+    /// Creates a random number generator with the given raw 48-bit state, as
+    /// returned by `state()`. Unlike `new`, the state isn't scrambled first.
+    pub fn from_state(state: u64) -> Self {
+        Random {
+            state: Wrapping(state as i64) & MASK,
+            next_next_gaussian: None,
         }
     }
 
@@ -61,7 +76,7 @@ impl Random {
 
     /// Returns a uniformly distributed signed 32-bit integer.
     pub fn next_i32(&mut self) -> i32 {
-        self.next(32) as i32
+        self.next(32)
     }
 
     /// Returns a uniformly distributed unsigned 32-bit integer.
@@ -135,14 +150,191 @@ impl Random {
         (high.wrapping_add(low) as f64) / F64_DIV
     }
 
+    /// Returns a normally distributed f64 with a mean of 0.0 and a standard deviation of 1.0.
+    /// 
+    /// Gaussians are made in pairs, so every other call doesn't step the RNG. The same as `nextGaussian` in Java.
+    pub fn next_gaussian(&mut self) -> f64 {
+        if let Some(gaussian) = self.next_next_gaussian.take() {
+            return gaussian;
+        }
+
+        loop {
+            let v1 = 2.0 * self.next_f64() - 1.0;
+            let v2 = 2.0 * self.next_f64() - 1.0;
+            let s = v1 * v1 + v2 * v2;
+            if s < 1.0 && s != 0.0 {
+                let multiplier = (-2.0 * strict_log(s) / s).sqrt();
+                self.next_next_gaussian = Some(v2 * multiplier);
+                return v1 * multiplier;
+            }
+        }
+    }
+
+    /// Returns an endless stream of `next_i32()`. The same as `ints()` in Java, use `take` for `ints(size)`.
+    pub fn ints(&mut self) -> impl Iterator<Item = i32> + '_ {
+        std::iter::repeat_with(move || self.next_i32())
+    }
+
+    /// Returns an endless stream of numbers in the range [origin, bound). The same as `ints(origin, bound)` in Java.
+    /// 
+    /// # Panics
+    /// If `origin` isn't less than `bound`, this function panics.
+    pub fn ints_in(&mut self, origin: i32, bound: i32) -> impl Iterator<Item = i32> + '_ {
+        if origin >= bound {
+            panic!("Origin must be < bound")
+        }
+
+        std::iter::repeat_with(move || {
+            let range = bound.wrapping_sub(origin);
+            if range > 0 {
+                return self.next_i32_bound(range) + origin;
+            }
+
+            // The range doesn't fit in an i32, so keep trying until one lands in it
+            loop {
+                let value = self.next_i32();
+                if value >= origin && value < bound {
+                    return value;
+                }
+            }
+        })
+    }
+
+    /// Returns an endless stream of `next_f64()`. The same as `doubles()` in Java, use `take` for `doubles(size)`.
+    pub fn doubles(&mut self) -> impl Iterator<Item = f64> + '_ {
+        std::iter::repeat_with(move || self.next_f64())
+    }
+
+    /// Returns an endless stream of numbers in the range [origin, bound). The same as `doubles(origin, bound)` in Java.
+    /// 
+    /// # Panics
+    /// If `origin` isn't less than `bound`, this function panics.
+    pub fn doubles_in(&mut self, origin: f64, bound: f64) -> impl Iterator<Item = f64> + '_ {
+        if origin.partial_cmp(&bound) != Some(Ordering::Less) {
+            panic!("Origin must be < bound")
+        }
+
+        std::iter::repeat_with(move || {
+            let value = self.next_f64() * (bound - origin) + origin;
+            // Rounding can land it right on the bound
+            if value >= bound {
+                bound.next_down()
+            } else {
+                value
+            }
+        })
+    }
+
+    /// Jumps ahead `steps` steps, as if `next` had been called that many
+    /// times, in O(log n) time. A cached Gaussian is kept.
+    pub fn advance(&mut self, steps: u64) {
+        // Stepping is x -> ax + c, and doing that twice is x -> a²x + (ac + c).
+        // Build up a^steps the same way as exponentiation by squaring.
+        let (mut multiplier, mut addend) = (Wrapping(1i64), Wrapping(0i64));
+        let (mut step_multiplier, mut step_addend) = (MULTIPLIER, ADDEND);
+        let mut steps = steps;
+        while steps > 0 {
+            if steps & 1 == 1 {
+                multiplier *= step_multiplier;
+                addend = addend * step_multiplier + step_addend;
+            }
+            step_addend = step_addend * step_multiplier + step_addend;
+            step_multiplier *= step_multiplier;
+            steps >>= 1;
+        }
+
+        self.state = (self.state * multiplier + addend) & MASK;
+    }
+
+    /// Returns the raw 48-bit state. Pass it to `from_state` to pick up from here.
+    pub fn state(&self) -> u64 {
+        self.state.0 as u64
+    }
+
     pub fn get_seed(&self) -> i64 {
         self.state.0
     }
 }
 
+// fdlibm's constants, bit for bit
+const LN2_HI: f64 = f64::from_bits(0x3FE62E42FEE00000);
+const LN2_LO: f64 = f64::from_bits(0x3DEA39EF35793C76);
+const TWO54: f64 = f64::from_bits(0x4350000000000000);
+const LG1: f64 = f64::from_bits(0x3FE5555555555593);
+const LG2: f64 = f64::from_bits(0x3FD999999997FA04);
+const LG3: f64 = f64::from_bits(0x3FD2492494229359);
+const LG4: f64 = f64::from_bits(0x3FCC71C51D8E78AF);
+const LG5: f64 = f64::from_bits(0x3FC7466496CB03DE);
+const LG6: f64 = f64::from_bits(0x3FC39A09D078C69F);
+const LG7: f64 = f64::from_bits(0x3FC2F112DF3E5244);
+const ONE_THIRD: f64 = f64::from_bits(0x3FD5555555555555);
+
+// The natural log, the way Java's StrictMath.log does it (fdlibm's e_log.c),
+// so Gaussians come out bit for bit the same as Java's on every platform.
+fn strict_log(x: f64) -> f64 {
+    let mut x = x;
+    let mut hx = (x.to_bits() >> 32) as i32;
+    let lx = x.to_bits() as u32;
+
+    let mut k: i32 = 0;
+    if hx < 0x00100000 {
+        if ((hx & 0x7fffffff) as u32 | lx) == 0 {
+            return f64::NEG_INFINITY;
+        }
+        if hx < 0 {
+            return f64::NAN;
+        }
+        // Subnormal, scale it up
+        k -= 54;
+        x *= TWO54;
+        hx = (x.to_bits() >> 32) as i32;
+    }
+    if hx >= 0x7ff00000 {
+        return x + x;
+    }
+    k += (hx >> 20) - 1023;
+    hx &= 0x000fffff;
+    let i = (hx + 0x95f64) & 0x100000;
+    // Normalize x or x/2
+    x = f64::from_bits(((hx | (i ^ 0x3ff00000)) as u64) << 32 | (x.to_bits() & 0xffffffff));
+    k += i >> 20;
+    let f = x - 1.0;
+    let dk = k as f64;
+
+    if (0x000fffff & (2 + hx)) < 3 {
+        // |f| < 2^-20
+        if f == 0.0 {
+            return if k == 0 { 0.0 } else { dk * LN2_HI + dk * LN2_LO };
+        }
+        let r = f * f * (0.5 - ONE_THIRD * f);
+        return if k == 0 { f - r } else { dk * LN2_HI - ((r - dk * LN2_LO) - f) };
+    }
+
+    let s = f / (2.0 + f);
+    let z = s * s;
+    let w = z * z;
+    let t1 = w * (LG2 + w * (LG4 + w * LG6));
+    let t2 = z * (LG1 + w * (LG3 + w * (LG5 + w * LG7)));
+    let r = t2 + t1;
+    if ((hx - 0x6147a) | (0x6b851 - hx)) > 0 {
+        let hfsq = 0.5 * f * f;
+        if k == 0 {
+            f - (hfsq - s * (hfsq + r))
+        } else {
+            dk * LN2_HI - ((hfsq - (s * (hfsq + r) + dk * LN2_LO)) - f)
+        }
+    } else if k == 0 {
+        f - s * (f - r)
+    } else {
+        dk * LN2_HI - ((s * (f - r) - dk * LN2_LO) - f)
+    }
+}
+
 // This is testing stuff
 // Rust file generated by Java
+#[cfg(test)]
 const RAND_NEXTBYTES_SEED: i64 = 0;
+#[cfg(test)]
 const RAND_NEXTBYTES: [u8; 128] = [
 	0x60, 0xB4, 0x20, 0xBB, 0x38, 0x51, 0xD9, 0xD4,
 	0x7A, 0xCB, 0x93, 0x3D, 0xBE, 0x70, 0x39, 0x9B,
@@ -161,7 +353,9 @@ const RAND_NEXTBYTES: [u8; 128] = [
 	0x08, 0x23, 0xF7, 0x8B, 0x5F, 0x8F, 0x5C, 0x02,
 	0x43, 0x9C, 0xE8, 0xF6, 0x7A, 0x78, 0x1D, 0x90,
 ];
+#[cfg(test)]
 const RAND_NEXT32_SEED: i64 = 123;
+#[cfg(test)]
 const RAND_NEXT32: [u32; 128] = [
 	0xB921F1DD, 0x3CBC0495, 0xFDAB8CD1, 0x4D33F0AA, 0x40D7D116, 0x92FA2632, 0x9BDA5745, 0x42460F3A,
 	0xCE4D76C3, 0x9F539C8A, 0xE01B0D5F, 0x8C9ED4FC, 0xB74EF490, 0x2986F7DA, 0x1269277B, 0xAE8B47EB,
@@ -181,7 +375,9 @@ const RAND_NEXT32: [u32; 128] = [
 	0x90F8CCB7, 0x82DE39B2, 0x83C41718, 0xBCFFF3C7, 0x27933FA6, 0x563A332A, 0x8D542823, 0xDB9A7661,
 ];
 
+#[cfg(test)]
 const RAND_NEXT64_SEED: i64 = 246;
+#[cfg(test)]
 const RAND_NEXT64: [u64; 128] = [
 	0xBC2EC2F174B9BA7A, 0x7105C369C188DAB7, 0x675B3ED6DBC9DF76, 0x90C83A7E3100BDC5, 0x915F0D0009C13A6D, 0x613F705DDAC80A80, 0xC60228F178DAE48D, 0x0DC22D3BB4876431,
 	0xC02820BB2E5E2B17, 0x2207E420DD6975B2, 0xA71F07A0AA6B372D, 0x1284A032F71A0F67, 0x5D186EFD2B49DEF0, 0xCB40023A4CC4B06D, 0x5CBE8962A0ABF3CA, 0xE2E0125F9FD5EF14,
@@ -200,7 +396,9 @@ const RAND_NEXT64: [u64; 128] = [
 	0x9CAE574E895A56FF, 0xA8ECA0C4F2DCFED9, 0xF3BB0B2864F46B74, 0xEE76CCFCCA6B1D3C, 0x7C5ECE4DBD5DABED, 0xD3909964FA4CD813, 0x532A37F25681CEAC, 0x752D748CF7FE1CAA,
 	0xE600BEB3DE75B967, 0xD650BFBB4B31BA45, 0x30E3994564DC7F19, 0x498A5A35B7A7C60C, 0xE8F7854CEF3DE716, 0x359A1CC02D71CA44, 0xA7E5988DF255D160, 0x65D1096353EE34BF,
 ];
+#[cfg(test)]
 const RAND_NEXT32_BOUND_65536_SEED: i64 = 369;
+#[cfg(test)]
 const RAND_NEXT32_BOUND_65536: [u32; 128] = [
 	0x0000BF24, 0x0000C034, 0x000091FD, 0x00006A2F, 0x0000D5DA, 0x00000D3C, 0x0000A6C4, 0x00004A98,
 	0x0000CF89, 0x00002BB0, 0x00009370, 0x0000C280, 0x0000251B, 0x0000F7D7, 0x0000DB0C, 0x00000379,
@@ -219,7 +417,9 @@ const RAND_NEXT32_BOUND_65536: [u32; 128] = [
 	0x000005CA, 0x000007CE, 0x0000AC81, 0x00000230, 0x000095FC, 0x0000C1F9, 0x0000F7A4, 0x0000E1BF,
 	0x00004972, 0x00009A6D, 0x00008F46, 0x00009839, 0x0000AED3, 0x0000FAF7, 0x000026AA, 0x0000E55E,
 ];
+#[cfg(test)]
 const RAND_NEXT32_BOUND_999999999_SEED: i64 = 369;
+#[cfg(test)]
 const RAND_NEXT32_BOUND_999999999: [u32; 128] = [
 	0x23F74229, 0x247F8570, 0x0D63B8EC, 0x3517F409, 0x2F529259, 0x069E5DCA, 0x17C74738, 0x254C77DD,
 	0x2C29C6CF, 0x15D82E1D, 0x0E1D503E, 0x25A56591, 0x128DE1D7, 0x31EBB28A, 0x01BCFF53, 0x1717A06F,
@@ -239,7 +439,9 @@ const RAND_NEXT32_BOUND_999999999: [u32; 128] = [
 	0x0ED7F1B4, 0x2027E505, 0x1FCFF777, 0x196B5AF6, 0x023CC975, 0x2C8DA51D, 0x2D6BB04D, 0x08BDDC24,
 ];
 
+#[cfg(test)]
 const RAND_NEXTBOOL_SEED: i64 = 492;
+#[cfg(test)]
 const RAND_NEXTBOOL: [bool; 128] = [
 	true, true, false, true, false, true, true, true,
 	false, false, true, true, true, true, false, false,
@@ -259,8 +461,10 @@ const RAND_NEXTBOOL: [bool; 128] = [
 	false, false, true, true, true, false, false, true,
 ];
 
+#[cfg(test)]
 const RAND_NEXTF32_SEED: i64 = 615;
 // f32 bit representation
+#[cfg(test)]
 const RAND_NEXTF32: [u32; 128] = [
 	0x3F2D1DA4, 0x3E572B3C, 0x3F55089A, 0x3D99E008, 0x3DB69008, 0x3F1E74FB, 0x3F0606C0, 0x3E468138,
 	0x3F4BD621, 0x3F069A1D, 0x3EF2E17E, 0x3E036F00, 0x3F5BB556, 0x3F0CE5B3, 0x3F012184, 0x3C95BB40,
@@ -280,8 +484,10 @@ const RAND_NEXTF32: [u32; 128] = [
 	0x3E001808, 0x3EA78034, 0x3ED97F96, 0x3CD19680, 0x3DC897B0, 0x3D4BFDC0, 0x3EB54FB0, 0x3F4812DA,
 ];
 
+#[cfg(test)]
 const RAND_NEXTF64_SEED: i64 = 738;
 // f64 bit representation
+#[cfg(test)]
 const RAND_NEXTF64: [u64; 128] = [
 	0x3FE6054EC36E442F, 0x3FD218B458790EBE, 0x3FCEAAB7DCE89694, 0x3FDEBD290205AFCC, 0x3FE1DCF6EF883DD8, 0x3FEF52A4437827B1, 0x3FED4D1156E1CD01, 0x3FDF1EA280AA9F6A,
 	0x3FD8E72476C9E63A, 0x3FEE61873F183AC4, 0x3FE647FD8CD4928F, 0x3FE4265C1D1E72F8, 0x3FE42B35001E70B9, 0x3F8DDE231A8E05C0, 0x3FE465A0D2ED4916, 0x3FD06107B50EE91C,
@@ -301,6 +507,88 @@ const RAND_NEXTF64: [u64; 128] = [
 	0x3F882C1D3DA490C0, 0x3FDB905898E3BBDA, 0x3FEE30CCAC544512, 0x3FE3E2744E099350, 0x3FE1249A39300313, 0x3FE47177EA8E89CA, 0x3FEFB625218C7ABF, 0x3FE4BA9A6ABF5387,
 ];
 
+#[cfg(test)]
+const RAND_NEXTGAUSSIAN_SEED: i64 = 861;
+// f64 bit representation
+#[cfg(test)]
+const RAND_NEXTGAUSSIAN: [u64; 128] = [
+	0xC0039AB75CA861B2, 0x3FF0A128EB5ABF0A, 0x3FDB13FD76232BDB, 0x3FC0F1668AFC83CF, 0x3FECA9A2117D247C, 0x3FE7BB91086162E2, 0xBFF08B2A1E9D184B, 0x400057C72BB2A478,
+	0xBFD559278401E43C, 0xBFA83C05420E2012, 0x3FD22049D0BDA595, 0xBFCAC4AD09AF7851, 0x3FD01BC8CD912CDD, 0xBF82D60DB212A32E, 0xBFCA7F5616D8F4B1, 0xBFCC70BD6918CB63,
+	0xBFEF57F2A5C38006, 0xBFE8330CAEE3F9A5, 0x3FDC58E149F2D6A8, 0xBFE1477F382795D6, 0x3FC04B37EEDD3AE8, 0x3FE6C9217AE0E6A2, 0xBFFA75BF52613713, 0xBFDE7A95BA9B0FB3,
+	0x3FEA82574088267E, 0x3FF8064554433287, 0xBFEB06A2199AB889, 0xBFF7563591723D2E, 0xBFD331E6DE36D26E, 0x3FF661857F043EAC, 0xBFE50249E5AAA9FA, 0x3FD5979996A9ABEF,
+	0xBFCB93D734706355, 0xBFA15B3B3DFA8599, 0x3FDF06401F924518, 0xBFC7746BB1E6120A, 0x3FE6B80188ACF15A, 0x4003F5E0C2DB1E25, 0xBFB8DF755D5E9FD0, 0x3FA24A847AE6263C,
+	0xBFF34397035CB2CA, 0xBFF0551025253591, 0xBFCF894C6BF1B1D8, 0xBFA38957B844E1C3, 0xBFD542846C9CC4AF, 0x3FDF5009C8B2CF1A, 0xBFF1F9FE38B009B7, 0xC0016C9604181944,
+	0xBFD2907224F4A142, 0x3FE8192836FD40EA, 0x3FDD69099C292493, 0x3FCD8242AC805431, 0x400B6F5455241087, 0x3FE0732A017A2EDB, 0xBFF2EEEDA49CA725, 0xBFD6D34872798EC6,
+	0xC0067E29ADA96928, 0x4005DBE8D72083B1, 0xBFF3AFE4E16AC100, 0x3FD91ECB81A4CF26, 0xBFF3DB57E6C18616, 0xBFEA9CC2FEEAFB8D, 0xBFB85F8E90BFF4DC, 0x3FF25A02E52F9867,
+	0xBFD326948A8EA34B, 0x3FE4BD4C6103B37E, 0x3FE7C45763ADB6CA, 0x3FE31393F685B17F, 0xBFECDAE974A98088, 0xBFEFA907A71A47D8, 0x3FF0B20967257753, 0x3FD3BD0FDFB50CCD,
+	0xBFD4E4DBD53ABF02, 0x4005BF1CB921B694, 0x40084156310CF7C1, 0xBFCDCA54C44D4FE3, 0x3FE086E476E14943, 0x3FE410D4894ED3E2, 0xBFC0620D5F25F298, 0xBFF9983586BAEBB5,
+	0x3FE488C783BFD611, 0xBFF3D3A4FD82A440, 0xBFED7048B6BDB7F2, 0xBFD0CB45AB863C6B, 0x3FB93CD335776F76, 0xBFC8DDC4F4A28B8D, 0x3FEDA439CD081EB6, 0x3FD776A9F1D5FB3D,
+	0x3FFAC1F6546CFB81, 0x3FDFC8272B75AA9C, 0xBFE7F793B41A0735, 0x3FF091C07EB78B95, 0x3FD0E6ADCBF5697F, 0x3FAB3DABE4976628, 0xBFE05F96E5E11B65, 0xBFFAF95F266D66AC,
+	0x3FE9B5626AE73177, 0x3FD1678E21787EE7, 0x3FF234892168A787, 0xBFA243250FF101B4, 0xBFF5BE67F23E019F, 0xBFF27B194FB7D624, 0x3FFB18F6857DD1BF, 0x4000A06227269A57,
+	0xC006C374435EDB2F, 0x3FDC18141F32D0B8, 0x400030989211C758, 0xBFF0DF31952AF1DC, 0x3FE3C860B41A9FB1, 0xBFE261F329BA44EA, 0x3FFB4A04EA10A188, 0x3FEF97EB7F12DBA0,
+	0xBFE055212704FF5B, 0xBFB58236FB615FDA, 0xBFC3FAA9121B000A, 0x3FD78041D8808A6E, 0x3FF0274A396438F8, 0x3FFA1DA31B7D7EC9, 0x3FE74FAD0F07E910, 0x3FF2AAD0CC1CA255,
+	0xBFE89A03F957D34B, 0x3FE6C67B4CF2CA53, 0xBFD9B13BAD9F01BA, 0x3FEA77CAFBEF90AF, 0xBFF4E27A16E01558, 0x3FB4155DA4F72F44, 0xBFC326668AC17059, 0x3FCE12CEAEF10428,
+];
+
+#[cfg(test)]
+const RAND_INTS_MINUS50_50_SEED: i64 = 984;
+#[cfg(test)]
+const RAND_INTS_MINUS50_50: [i32; 128] = [
+	11, -14, 16, 35, -44, 18, -48, -26, 8, -19, -36, 28, -30, -40, -8, -3,
+	41, -25, -30, 5, -17, -9, -32, -32, 29, -22, 15, -19, -4, -41, -38, 44,
+	17, -36, -37, 26, -27, 46, -8, 31, 28, 12, 7, -24, -38, -7, 19, -26,
+	4, -12, 49, 8, 25, 41, 46, -36, 39, -17, 20, 10, 10, -28, -44, -25,
+	-37, 29, -48, -4, 5, -36, 14, -44, 27, -44, -15, 10, 26, 36, 41, -16,
+	12, -13, -43, 46, -24, 0, 9, -13, 41, 22, -24, -17, -47, 23, -44, 14,
+	-46, -37, 31, 36, 32, 32, 38, -2, -10, 0, 2, -28, -17, -44, -18, -15,
+	29, 48, -44, -35, 40, -13, 8, 33, -22, 41, -30, -14, -21, 36, 48, 0,
+];
+#[cfg(test)]
+const RAND_INTS_MINUS2000000000_2000000000_SEED: i64 = 1230;
+// i32 bit representation
+#[cfg(test)]
+const RAND_INTS_MINUS2000000000_2000000000: [u32; 128] = [
+	0xA4E206BE, 0xCAEDDD51, 0xB2D780AD, 0xAB07E29E, 0xD36F3FA4, 0xADA67323, 0x5CB0E824, 0xB8C92076,
+	0xB3AC6EA7, 0x1C421C5F, 0x0B87CB0B, 0x36C8E906, 0x8B9BB98B, 0xC25215CF, 0x5BA4B637, 0x191897C8,
+	0xD9E8AE07, 0x41731D98, 0xE63378FC, 0xF407124F, 0xD42622ED, 0x4F1CA789, 0x30EFE4F2, 0xFDA886BE,
+	0x2DA6878E, 0x9E3E82B7, 0xC57845FC, 0x2F771D7E, 0xB27F455B, 0x249AC449, 0xD500D686, 0x5AE0922B,
+	0xD82E17CF, 0xFC530B8F, 0x4713B40D, 0xC85FE7CE, 0xCB617E8C, 0xC9FD7359, 0x52BD17E4, 0x7701AC51,
+	0x16D3ADC4, 0xD006C486, 0xF13BD834, 0xED247F2F, 0xC439A0DA, 0x266CBDDB, 0x044A7543, 0x5A2ECF6A,
+	0xB43E3348, 0x73348515, 0xE09536F9, 0x570E04C2, 0xE79B63C2, 0xD9AE9540, 0xC3361EA0, 0x1FE28823,
+	0xFFE2259C, 0x15DABE49, 0x4757B0EB, 0x9F7F5F5E, 0x6CA36468, 0x235409D2, 0x33ABDC39, 0xFC9B501C,
+	0xBD559945, 0x6E747F1A, 0xA3FCBE0E, 0x1B68B610, 0x24E6D12B, 0xD6AF5311, 0x99C8B869, 0x564DE9A5,
+	0x2C4E65BE, 0x71CF3F9B, 0x71B0EA91, 0x6AE002A6, 0xA0D11CBC, 0x5B895970, 0xE1436414, 0x9E1D3350,
+	0xF6BA587D, 0x40DC1208, 0xDC5A6BD9, 0x9ED23B37, 0x14F9C047, 0xBCDB5B60, 0x70D9D298, 0xC83ABAB2,
+	0x568299E1, 0xB7CA788C, 0xDEC8D876, 0x354EA866, 0x120ACF31, 0xB5BBFA67, 0x640F1D80, 0x8EAFA45A,
+	0xF0F70D9C, 0xA65FB986, 0xB911AB38, 0x57B7A102, 0xA1F6B9F5, 0x1ECFB366, 0x92F22FD3, 0xCC31F2EF,
+	0x1EFDDE0A, 0x9226DC55, 0x073C3CF8, 0x582B6BDC, 0xB0294405, 0xEC37F16C, 0xD14DE4A6, 0x9F47B7F0,
+	0x318AF5BC, 0xB3B2C054, 0xD8E12707, 0xB1F75D7A, 0xE198CEC6, 0xAD05C659, 0x04919266, 0x021C1DFA,
+	0x6BBFB430, 0x7250918E, 0x154072B4, 0x7724FF31, 0x98485EB7, 0xB4C20F5B, 0x0C4F625E, 0x1818470C,
+];
+
+#[cfg(test)]
+const RAND_DOUBLES_MINUS1_5_2_5_SEED: i64 = 1107;
+// f64 bit representation
+#[cfg(test)]
+const RAND_DOUBLES_MINUS1_5_2_5: [u64; 128] = [
+	0x3FF0A444DF706B36, 0x3FFCA53AD9421DE0, 0x3FCA16E20FDED180, 0xBF85A41C66ED4C00, 0xBFF20A46A0A37A0E, 0xBFF77FE83E3D9458, 0x400336AA4FADBF18, 0x3FFC2F5FFCB688A0,
+	0x3FF61BFEAEE2E650, 0x3FDA754918069118, 0xBFE9DC539F979154, 0xBFDA66B38535BAB8, 0xBFF36F8F855D2FF4, 0x3FEA21F4EB6553B8, 0xBFE7C7CAEA5A1898, 0xBFE0E582F2B1AFAC,
+	0x400214F3DDBB8DBC, 0x4003047996AF0CEE, 0x3FE0502469AF05D8, 0xBFF5F8D2F604503C, 0xBFE45CF8A743C884, 0xBFF42C1BE3816F6E, 0x4000EAD46937CC82, 0x3FFB8603124DF8BA,
+	0xBFD353E068CBE738, 0x3FDE5F835EFB5698, 0xBFA95475AD73BA80, 0x4001C077FABCD751, 0x3FF88F4DF5FDFA8E, 0x3FEAF686909C2344, 0x3FE62851CB578F04, 0x4000C628FAB6AF11,
+	0xBFAF16AD6E867740, 0xBFE25B8B50BBA6F8, 0xBFC615BAA86BD930, 0x4002E6C7904399F8, 0x3FD9189F6BCAB320, 0xBFF54C73721B8AC4, 0xBFF4E7B93367D072, 0x3FE52AC458AF29B4,
+	0xBFF00F0DB4AF293E, 0xBFAB1E2AEAE9B880, 0x3FF059E7BA7A4E3C, 0x3FE3F634EC975DA8, 0x3FF0D25AF3065A76, 0x3FAE09C6EE575F80, 0x4003E73243F7D0C9, 0xBFF4DCAE8819DD04,
+	0x4000F4866FCB957B, 0x3FFDC7F05BF2BA7E, 0xBFE2D90B3BCE83D0, 0xBFC29B698980BB20, 0x3FFA8F1679768C70, 0x3FD299115310EB70, 0xBFEF2E62BCA1D210, 0xBFB738733D076BE0,
+	0xBFF1B71865D8FC8A, 0xBFECDCEBEA3FCC58, 0x3FFA0D25E54F99C0, 0xBFEF1084DB4E5490, 0x3FFC1A62B95DE54A, 0x4003193669EAC321, 0x3FDBBBC01F07ECC8, 0xBFF5404093F4CBEC,
+	0xBFF56F7D186256E4, 0x3FD904C875089AD0, 0x3FBC6A798C1D5040, 0x40022FFCAE8C535E, 0xBFEA03D56803CB44, 0xBFF151E46F1A7712, 0x3FFC0185BE52B4B0, 0x3FEB8BDF82E758E4,
+	0xBF917E54589CFF80, 0xBFF7C8817BF0058E, 0x3FD8CC2542B0ECE0, 0x40015708A2A30CAF, 0xBFCC11D6F42E99B0, 0x3FE3E01BE221D418, 0xBFE7D859A9612424, 0xBFBBFF4F928F6F60,
+	0x3FEAA46BEC6D3914, 0xBFBF82692530DB20, 0xBFF71BAADD25A7C8, 0x40033288BCD504BE, 0x3FEF937D6121C4E8, 0x40017D3FBF43D694, 0x3FFD59D610B31E64, 0xBFDF758BDA698358,
+	0x3FF395E35B16E45A, 0xBFE5D5D7065D1F3C, 0xBFB73B68C585EA00, 0x3FFD941AB2B34214, 0xBFD4A421C8620400, 0x3FE02C7A62DA2664, 0x3FEB56F55E45A724, 0xBFB11EF6F910C3E0,
+	0xBFF02B9B79ECA0A8, 0x3FFE7AFD5F25DCB6, 0xBFF56DF30663216E, 0xBFEA7592B7BEE364, 0xBFE5C7FAE1168650, 0x3FFBDABA2471BB82, 0x3FEF399E5CDE73F8, 0x3FED480693B9D8D4,
+	0x3FD95C1A2A6452C8, 0xBFF67B0EBB6019D4, 0xBFD20C74E318B330, 0x3FFFC92BD1510A70, 0x3FE6E607C9380C58, 0xBFF79CDCD7C7FB32, 0xBFE3E98F84EB33A8, 0x3FD2E665FF750170,
+	0x3FD436075E59BF68, 0xBFDC3A394D37F1B8, 0x3FDDCF78B9EC9CB8, 0x3FFB20517A4AB4E4, 0x3FF860A53F88EDEE, 0xBFD83E348DB54728, 0x3FFD5FA4FF81DDAA, 0xBFD6E4B6C0DB5A30,
+	0xBFD702479A4C9A00, 0x4000215BB12E9A4B, 0xBFDFB5A4E94DC040, 0x3FB4063BB873F3C0, 0xBFF3FFF6AFA5172C, 0xBFE38EF4CAA48F90, 0x3FF1C5C2AF0CE006, 0x3FF8B2DBDE5B5536,
+];
+
 #[test]
 fn test_nextbytes() {
     let mut random = Random::new(RAND_NEXTBYTES_SEED);
@@ -400,4 +688,73 @@ fn test_nextf64() {
             panic!("mismatch at index {}: expected {}, got {}", index, elem, gen);
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_nextgaussian() {
+    let mut random = Random::new(RAND_NEXTGAUSSIAN_SEED);
+
+    for (index, &elem) in RAND_NEXTGAUSSIAN.iter().enumerate() {
+        let gen = random.next_gaussian().to_bits();
+
+        if gen != elem {
+            panic!("mismatch at index {}: expected {}, got {}", index, elem, gen);
+        }
+    }
+}
+
+#[test]
+fn test_ints_minus50_50() {
+    let mut random = Random::new(RAND_INTS_MINUS50_50_SEED);
+
+    for (index, (gen, &elem)) in random.ints_in(-50, 50).zip(RAND_INTS_MINUS50_50.iter()).enumerate() {
+        if gen != elem {
+            panic!("mismatch at index {}: expected {}, got {}", index, elem, gen);
+        }
+    }
+}
+
+#[test]
+fn test_ints_minus2000000000_2000000000() {
+    let mut random = Random::new(RAND_INTS_MINUS2000000000_2000000000_SEED);
+
+    for (index, (gen, &elem)) in random.ints_in(-2000000000, 2000000000).zip(RAND_INTS_MINUS2000000000_2000000000.iter()).enumerate() {
+        if gen as u32 != elem {
+            panic!("mismatch at index {}: expected {}, got {}", index, elem, gen);
+        }
+    }
+}
+
+#[test]
+fn test_doubles_minus1_5_2_5() {
+    let mut random = Random::new(RAND_DOUBLES_MINUS1_5_2_5_SEED);
+
+    for (index, (gen, &elem)) in random.doubles_in(-1.5, 2.5).zip(RAND_DOUBLES_MINUS1_5_2_5.iter()).enumerate() {
+        if gen.to_bits() != elem {
+            panic!("mismatch at index {}: expected {}, got {}", index, elem, gen);
+        }
+    }
+}
+
+#[test]
+fn test_advance() {
+    let mut stepped = Random::new(RAND_NEXT32_SEED);
+    for steps in [0, 1, 2, 5, 64, 1000] {
+        let mut jumped = stepped;
+        jumped.advance(steps);
+        for _ in 0..steps {
+            stepped.next(32);
+        }
+        assert_eq!(jumped.state(), stepped.state());
+    }
+
+    let mut random = Random::new(RAND_NEXT32_SEED);
+    random.advance(100);
+    assert_eq!(random.next_u32(), RAND_NEXT32[100]);
+    assert_eq!(Random::from_state(random.state()).next_u32(), RAND_NEXT32[101]);
+
+    // A whole period comes back around
+    let mut random = Random::new(RAND_NEXT32_SEED);
+    random.advance(1 << 48);
+    assert_eq!(random.next_u32(), RAND_NEXT32[0]);
+}
@@ -1,11 +1,260 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
 use crate::utils::EntityId;
 use crate::game::Game;
+use crate::modifiers::ModifierDuration;
+use crate::rng::RngKind;
+use crate::rules::Rules;
+use crate::seed::Seed;
+use crate::player::Player;
+use crate::team::Team;
+
+/// Every player in the league, stored by id.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Players {
+  players: BTreeMap<EntityId, Player>,
+}
+
+impl Players {
+  pub fn empty() -> Self {
+    Players {
+      players: BTreeMap::new(),
+    }
+  }
+
+  /// Adds the player, replacing any player that has the same id.
+  pub fn insert(&mut self, player: Player) {
+    self.players.insert(player.id, player);
+  }
+
+  pub fn get(&self, id: EntityId) -> Option<&Player> {
+    self.players.get(&id)
+  }
+
+  pub fn remove(&mut self, id: EntityId) -> Option<Player> {
+    self.players.remove(&id)
+  }
+
+  pub fn get_mut(&mut self, id: EntityId) -> Option<&mut Player> {
+    self.players.get_mut(&id)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &Player> {
+    self.players.values()
+  }
+
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Player> {
+    self.players.values_mut()
+  }
+
+  pub fn len(&self) -> usize {
+    self.players.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.players.is_empty()
+  }
+
+  pub fn find_by_name(&self, name: &str) -> Option<&Player> {
+    self.iter().find(|player| player.name == name)
+  }
+}
+
+/// Why a team couldn't be added to the league. The league is left as it was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeagueError {
+  DuplicateTeam(EntityId), // Team ID that's already in the league
+}
+
+impl fmt::Display for LeagueError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LeagueError::DuplicateTeam(id) => write!(f, "there's already a team with id {} in the league", id),
+    }
+  }
+}
+
+impl std::error::Error for LeagueError {}
+
+/// Every team in the league, stored by id.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Teams {
+  teams: BTreeMap<EntityId, Team>,
+}
+
+impl Teams {
+  pub fn empty() -> Self {
+    Teams {
+      teams: BTreeMap::new(),
+    }
+  }
+
+  /// Adds the team. Fails if there's already a team with the same id.
+  pub fn insert(&mut self, team: Team) -> Result<(), LeagueError> {
+    if self.teams.contains_key(&team.get_id()) {
+      return Err(LeagueError::DuplicateTeam(team.get_id()));
+    }
+    self.teams.insert(team.get_id(), team);
+    Ok(())
+  }
+
+  /// Stores the team, replacing the team that has the same id, which is returned.
+  pub fn replace(&mut self, team: Team) -> Option<Team> {
+    self.teams.insert(team.get_id(), team)
+  }
+
+  pub fn get(&self, id: EntityId) -> Option<&Team> {
+    self.teams.get(&id)
+  }
+
+  pub fn get_mut(&mut self, id: EntityId) -> Option<&mut Team> {
+    self.teams.get_mut(&id)
+  }
+
+  /// Returns the ids of every team, in ascending order.
+  pub fn ids(&self) -> Vec<EntityId> {
+    self.teams.keys().copied().collect()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &Team> {
+    self.teams.values()
+  }
+
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Team> {
+    self.teams.values_mut()
+  }
+
+  pub fn len(&self) -> usize {
+    self.teams.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.teams.is_empty()
+  }
+
+  pub fn find_by_name(&self, name: &str) -> Option<&Team> {
+    self.iter().find(|team| team.get_name() == name || team.get_team_name() == name)
+  }
+}
+
+/// A single game on the schedule. The result is filled in once it's been played.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduledGame {
+  pub day: usize,
+  pub home: EntityId,
+  pub away: EntityId,
+  pub result: Option<(f64, f64)>, // Home score, Away score
+}
+
+/// The league schedule. Days start at 1, like they do in `Game`.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Games {
+  days: Vec<Vec<ScheduledGame>>,
+}
+
+impl Games {
+  pub fn empty() -> Self {
+    Games {
+      days: Vec::new(),
+    }
+  }
+
+  /// Generates a round-robin schedule for `days` days using the circle method.
+  /// Every team plays once a day (teams get a bye on days when there's an odd
+  /// number of them), and every team plays every other team once before any
+  /// matchup repeats. Home and away swap every time the schedule repeats.
+  pub fn round_robin(teams: &[EntityId], days: usize) -> Self {
+    let mut rotation: Vec<Option<EntityId>> = teams.iter().map(|id| Some(*id)).collect();
+    if rotation.len() % 2 == 1 {
+      rotation.push(None); // Bye
+    }
+    let slots = rotation.len();
+    let rounds = slots.saturating_sub(1);
+
+    let mut schedule = Games::empty();
+    if rounds == 0 {
+      return schedule;
+    }
+
+    for day in 1..=days {
+      let round = (day - 1) % rounds;
+      let cycle = (day - 1) / rounds;
+
+      let mut games = Vec::new();
+      for i in 0..slots / 2 {
+        if let (Some(first), Some(second)) = (rotation[i], rotation[slots - 1 - i]) {
+          let (home, away) = if (round + i + cycle).is_multiple_of(2) {
+            (first, second)
+          } else {
+            (second, first)
+          };
+          games.push(ScheduledGame {
+            day,
+            home,
+            away,
+            result: None,
+          });
+        }
+      }
+      schedule.days.push(games);
+
+      // Keep the first slot fixed and rotate everyone else
+      rotation[1..].rotate_right(1);
+    }
+    schedule
+  }
+
+  /// Returns the games played on `day`.
+  pub fn get_day(&self, day: usize) -> Option<&Vec<ScheduledGame>> {
+    day.checked_sub(1).and_then(|index| self.days.get(index))
+  }
+
+  pub fn get_day_mut(&mut self, day: usize) -> Option<&mut Vec<ScheduledGame>> {
+    day.checked_sub(1).and_then(move |index| self.days.get_mut(index))
+  }
+
+  /// Returns the number of days on the schedule.
+  pub fn len(&self) -> usize {
+    self.days.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.days.is_empty()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &ScheduledGame> {
+    self.days.iter().flatten()
+  }
+
+  /// Returns the first day that still has unplayed games.
+  pub fn next_day(&self) -> Option<usize> {
+    self.days
+      .iter()
+      .position(|games| games.iter().any(|game| game.result.is_none()))
+      .map(|index| index + 1)
+  }
+}
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct League {
   pub players: Players,
   pub teams: Teams,
   pub games: Games,
+  pub hall_of_the_dead: Players, // Every player that's been incinerated
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub rules: Rules, // Every game in the league is played by these
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub seed: i64, // Every game's seed is derived from this one, see `game_seed()`
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub season: usize,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub rng: RngKind, // What every game rolls with
 }
 
 impl League {
@@ -14,6 +263,11 @@ impl League {
       players,
       teams,
       games,
+      hall_of_the_dead: Players::empty(),
+      rules: Rules::default(),
+      seed: 0,
+      season: 0,
+      rng: RngKind::Java,
     }
   }
 
@@ -22,8 +276,257 @@ impl League {
       players: Players::empty(),
       teams: Teams::empty(),
       games: Games::empty(),
+      hall_of_the_dead: Players::empty(),
+      rules: Rules::default(),
+      seed: 0,
+      season: 0,
+      rng: RngKind::Java,
+    }
+  }
+
+  /// The seed for the `index`th game on `day` of the current season. The
+  /// same league seed always plays the same season.
+  pub fn game_seed(&self, day: usize, index: usize) -> i64 {
+    Seed::new(self.seed).season(self.season).day(day).game(index).value()
+  }
+
+  /// Adds a team to the league, along with all of its players.
+  /// Fails if there's already a team with the same id.
+  pub fn add_team(&mut self, team: Team) -> Result<(), LeagueError> {
+    if self.teams.get(team.get_id()).is_some() {
+      return Err(LeagueError::DuplicateTeam(team.get_id()));
+    }
+    for player in team.players() {
+      self.players.insert(player.clone());
+    }
+    self.teams.insert(team)
+  }
+
+  /// Finds a team by either its name or its full name.
+  pub fn find_by_name(&self, name: &str) -> Option<&Team> {
+    self.teams.find_by_name(name)
+  }
+
+  /// Replaces the schedule with a round-robin regular season of `days` days
+  /// between every team currently in the league.
+  pub fn generate_schedule(&mut self, days: usize) {
+    self.games = Games::round_robin(&self.teams.ids(), days);
+  }
+
+  /// Simulates every game on `day` and writes the results back into the league.
+  /// Anyone incinerated during a game is moved to the hall of the dead.
+  /// Returns the finished games so their logs can be read.
+  ///
+  /// # Panics
+  /// If a scheduled team isn't in the league, or the league's rules don't
+  /// pass `Rules::validate()`, this function panics.
+  pub fn play_day(&mut self, day: usize) -> Vec<Game> {
+    let matchups: Vec<(EntityId, EntityId)> = match self.games.get_day(day) {
+      Some(games) => games.iter().map(|game| (game.home, game.away)).collect(),
+      None => return Vec::new(),
+    };
+
+    let mut played = Vec::new();
+    for (index, (home, away)) in matchups.into_iter().enumerate() {
+      let home_team = self.teams.get(home).expect("scheduled team isn't in the league").clone();
+      let away_team = self.teams.get(away).expect("scheduled team isn't in the league").clone();
+
+      let mut game = Game::with_rng_kind(home_team, away_team, day, 0, self.rules, self.rng, self.game_seed(day, index));
+      game.simulate_game();
+
+      let (home_team, away_team) = game.get_teams();
+      self.update_team(home_team);
+      self.update_team(away_team);
+      for player in game.incinerated.iter() {
+        self.players.remove(player.id);
+        self.hall_of_the_dead.insert(player.clone());
+      }
+      if let Some(games) = self.games.get_day_mut(day) {
+        games[index].result = Some(game.scores);
+      }
+
+      played.push(game);
+    }
+    played
+  }
+
+  /// Plays every day that hasn't been played yet.
+  pub fn simulate_season(&mut self) {
+    while let Some(day) = self.games.next_day() {
+      self.play_day(day);
+    }
+  }
+
+  /// Clears every player's season stats, for when a new season starts.
+  pub fn clear_statistics(&mut self) {
+    for team in self.teams.iter_mut() {
+      for player in team.players_mut() {
+        player.clear_statistics();
+      }
+    }
+    for player in self.players.iter_mut() {
+      player.clear_statistics();
+    }
+  }
+
+  /// Removes the modifiers that only last as long as `duration` from every team and player.
+  /// Call it with `ModifierDuration::Season` when a new season starts.
+  pub fn expire_modifiers(&mut self, duration: ModifierDuration) {
+    for team in self.teams.iter_mut() {
+      team.expire_modifiers(duration);
+    }
+    for player in self.players.iter_mut() {
+      player.expire_modifiers(duration);
     }
   }
 
-  pub fn find_by_name
-}
\ No newline at end of file
+  /// Returns every team sorted by wins, then by fewest losses.
+  pub fn standings(&self) -> Vec<&Team> {
+    let mut standings: Vec<&Team> = self.teams.iter().collect();
+    standings.sort_by(|a, b| b.get_wins().cmp(&a.get_wins()).then(a.get_losses().cmp(&b.get_losses())));
+    standings
+  }
+
+  // Stores the team along with its players, since games
+  // hand back copies instead of the originals.
+  fn update_team(&mut self, team: Team) {
+    for player in team.players() {
+      self.players.insert(player.clone());
+    }
+    self.teams.replace(team);
+  }
+}
+
+#[test]
+fn test_round_robin_plays_everyone_once() {
+  let teams: Vec<EntityId> = (0..6).collect();
+  let schedule = Games::round_robin(&teams, 5);
+
+  assert_eq!(schedule.len(), 5);
+  for day in 1..=5 {
+    let games = schedule.get_day(day).unwrap();
+    assert_eq!(games.len(), 3);
+
+    let mut playing: Vec<EntityId> = games.iter().flat_map(|game| [game.home, game.away]).collect();
+    playing.sort();
+    assert_eq!(playing, teams);
+  }
+
+  let mut matchups: Vec<(EntityId, EntityId)> = schedule.iter().map(|game| (game.home.min(game.away), game.home.max(game.away))).collect();
+  matchups.sort();
+  matchups.dedup();
+  assert_eq!(matchups.len(), 15);
+}
+
+#[test]
+fn test_round_robin_gives_byes() {
+  let teams: Vec<EntityId> = (0..5).collect();
+  let schedule = Games::round_robin(&teams, 10);
+
+  for games in schedule.days.iter() {
+    assert_eq!(games.len(), 2);
+  }
+  assert_eq!(schedule.next_day(), Some(1));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip_league() {
+  use crate::java_random::Random;
+
+  let mut rng = Random::new(3);
+  let mut league = League::empty();
+  for _ in 0..4 {
+    league.add_team(Team::empty(&mut rng)).unwrap();
+  }
+  league.generate_schedule(6);
+  league.play_day(1);
+
+  let saved = serde_json::to_string(&league).unwrap();
+  let mut loaded: League = serde_json::from_str(&saved).unwrap();
+  assert_eq!(serde_json::to_string(&loaded).unwrap(), saved);
+
+  league.simulate_season();
+  loaded.simulate_season();
+  assert_eq!(serde_json::to_string(&loaded).unwrap(), serde_json::to_string(&league).unwrap());
+}
+
+#[test]
+fn test_seasons_replay_from_the_seed() {
+  use crate::team_generator::TeamGenerator;
+
+  let play = |seed: i64| {
+    let mut generator = TeamGenerator::new(1);
+    let mut league = League::empty();
+    league.seed = seed;
+    for _ in 0..4 {
+      let mut team = generator.next_team();
+      team.set_favor(0); // Same favor, same day
+      league.add_team(team).unwrap();
+    }
+    league.generate_schedule(3);
+    let games: Vec<Game> = (1..=3).flat_map(|day| league.play_day(day)).collect();
+    games
+  };
+
+  let games = play(5);
+  let again = play(5);
+  for (game, other) in games.iter().zip(again.iter()) {
+    assert_eq!(game.events, other.events);
+  }
+  assert_ne!(League::empty().game_seed(1, 0), League::empty().game_seed(1, 1));
+  assert!(play(6).iter().zip(games.iter()).any(|(game, other)| game.events != other.events));
+}
+
+#[test]
+fn test_add_team_rejects_duplicate_ids() {
+  use crate::java_random::Random;
+
+  let mut league = League::empty();
+  let team = Team::empty(&mut Random::new(4));
+  league.add_team(team.clone()).unwrap();
+  assert_eq!(league.add_team(team.clone()), Err(LeagueError::DuplicateTeam(team.get_id())));
+  assert_eq!(league.teams.len(), 1);
+
+  // Making a team only rolls for its players
+  let mut rng = Random::new(4);
+  let mut players_only = Random::new(4);
+  Team::empty(&mut rng);
+  Team::generate_new_players(&mut players_only, 14);
+  assert_eq!(rng.next_i64(), players_only.next_i64());
+}
+
+#[test]
+fn test_play_day_updates_records() {
+  use crate::java_random::Random;
+
+  let mut rng = Random::new(13);
+  let mut league = League::empty();
+  for _ in 0..4 {
+    league.add_team(Team::empty(&mut rng)).unwrap();
+  }
+  league.generate_schedule(3);
+
+  // Each team played once on day 1. The home team only wins if they outscored the away team
+  for game in league.play_day(1) {
+    let (winner, loser) = if game.scores.0 > game.scores.1 {
+      (game.home.get_id(), game.away.get_id())
+    } else {
+      (game.away.get_id(), game.home.get_id())
+    };
+    let winner = league.teams.get(winner).unwrap();
+    let loser = league.teams.get(loser).unwrap();
+    assert_eq!((winner.get_wins(), winner.get_losses(), winner.get_non_losses()), (1, 0, 1));
+    assert_eq!((loser.get_wins(), loser.get_losses(), loser.get_non_losses()), (0, 1, 0));
+  }
+
+  // Everyone plays every day, and every game has one winner and one loser
+  league.simulate_season();
+  for team in league.teams.iter() {
+    assert_eq!(team.get_wins() + team.get_losses(), 3);
+    assert_eq!(team.get_wins(), team.get_non_losses() as i32);
+  }
+  let wins: i32 = league.teams.iter().map(Team::get_wins).sum();
+  let losses: i32 = league.teams.iter().map(Team::get_losses).sum();
+  assert_eq!((wins, losses), (6, 6));
+}
@@ -1,12 +1,29 @@
 pub mod java_random;
+pub mod rng;
+pub mod seed;
 pub mod game;
+pub mod rules;
+pub mod event;
 pub mod weather;
 pub mod team;
+pub mod team_generator;
 pub mod name_generator;
 pub mod markov_chain;
 pub mod player;
 pub mod player_stats;
+pub mod scouting;
+pub mod modifiers;
+pub mod statistics;
+pub mod box_score;
 pub mod messaging;
 pub mod player_flavor;
 pub mod league;
+pub mod importer;
+pub mod parity;
+pub mod clock;
+pub mod playback;
+#[cfg(feature = "serde")]
+pub mod snapshot;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod utils;
\ No newline at end of file
@@ -1,58 +1,584 @@
-use std::time::{Duration, SystemTime};
-
-use java_random::Random;
-
-fn main() {
-    let mut rng = Random::new(-4);
-
-    let mut game = game::Game::new(
-        team::Team::empty(&mut rng),
-        team::Team::empty(&mut rng),
-        4,
-        SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis(),
-    );
-    game.simulate_game();
-    game.play_logs();
-}
-
-// Thought on structure
-// Create a Game struct that has all the information it needs to run
-// Give the struct a .next() method that returns the next state of the game
-// Give the struct a .is_over() method that returns true if the game is over
-// Iterate through each game and increment the progress of the game until the game is over
-
-// How the program works:
-// Loads data for games from the JSON database
-// Runs a game for each game in the database
-// Each tick's results will be added to a queue of messages
-// They're printed at the end of the tick
-// When all games are over, the program ends
-// Then the results of the games are added to the database and updated
-
-// struct Game {
-//     rng: u32, // Change later. Needs to be rng,
-//     game_logs: Vec<String>,
-// }
-
-// impl Game {
-//     fn new() -> Game {
-//         // Instantiate game
-//     }
-
-//     fn next(&self) {
-//         // run next state of game
-//         let mut next = self.clone();
-
-//         // Run loop
-
-//         self = next;
-//     }
-
-//     fn is_over(&self) -> bool {
-//         // Check if game is over
-//     }
-
-//     fn get_logs(&self) -> Vec<String> {
-//         // Get logs
-//     }
-// }
+//! The `albtrs` command line. Run `albtrs --help` for the full list of commands.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+
+use alt_league_blaseball::clock::{Clock, SystemClock};
+use alt_league_blaseball::game::Game;
+use alt_league_blaseball::importer::{self, ImportError};
+use alt_league_blaseball::java_random::Random;
+use alt_league_blaseball::league::League;
+use alt_league_blaseball::messaging::MessageLog;
+use alt_league_blaseball::modifiers::ModifierDuration;
+use alt_league_blaseball::playback::{Playback, Speed};
+use alt_league_blaseball::player::Player;
+use alt_league_blaseball::rng::RngKind;
+use alt_league_blaseball::rules::Rules;
+use alt_league_blaseball::seed::{Seed, Subsystem};
+use alt_league_blaseball::team::{LineupStrategy, Team};
+use alt_league_blaseball::team_generator::TeamGenerator;
+
+const USAGE: &str = "\
+Usage: albtrs <command> [options]
+
+Commands:
+  game <home> <away>      Plays a game between two teams
+      --league <league>     Looks the teams up by name in a league instead of reading team files
+      --day <n>             Day of the season the game is played on (default 1)
+      --seed <n>            Seeds the game directly instead of working it out from the day and teams
+      --save-log <file>     Saves the game's messages so they can be played back with `replay`
+      --box-score <file>    Saves the box score, as a table or as JSON depending on --format
+      --lineup <strategy>   Batting order for both teams: manual, batting, obp or shuffle
+                            (default: each team's own)
+      --rng <kind>          What the game rolls with: java, to roll the same as the Java
+                            version, or counter (default java)
+      Also takes the rule options below
+  season [<league>]       Plays a whole regular season
+      --teams <n>           Generates n random teams when no league is given (default 8)
+      --seed <n>            The league's seed, which the teams and every game are
+                            generated from (default 0, or the league's own)
+      --days <n>            Length of the season (default 99)
+      --save <file>         Saves the league once the season is over
+      --lineup <strategy>   Batting order for every team, like for `game`
+      --rng <kind>          What every game rolls with, like for `game`, saved with the league
+                            (default java, or the league's own)
+      Also takes the rule options below, which are saved with the league
+  gen-player              Generates random players
+      --count <n>           How many players to generate (default 1)
+      --seed <n>            (default: the current time)
+  gen-team                Generates a random team
+      --name <name>, --location <location>, --logo <logo>,
+      --abbreviation <abbreviation>, --favor <n>
+                            Picked at random when left out
+      --seed <n>            (default: the current time)
+  replay <log>            Plays back a log saved by `game --save-log`
+      --speed <speed>       1x, 10x or any other multiplier, or instant (default 1x)
+
+Rule options, for variants like fourth strike or a fourth base:
+  --strikes <n>           Strikes for a strikeout (default 3)
+  --balls <n>             Balls for a walk (default 4)
+  --outs <n>              Outs in a half inning (default 3)
+  --innings <n>           Innings before the game can end (default 9)
+  --bases <n>             Bases before home (default 3)
+
+Every command takes:
+  --format <format>       text, or json for one JSON object per line (default text)
+  -h, --help              Shows this message
+
+Team files are JSON, like the ones `gen-team --format json` writes. A league is
+either a JSON file written by `season --save`, or a Java league save directory.
+
+Exit codes:
+  0  Success
+  1  A file couldn't be read or written
+  2  Invalid command or options
+  3  A team, league or log file is invalid
+";
+
+const RULE_OPTIONS: [&str; 5] = ["strikes", "balls", "outs", "innings", "bases"];
+const DEFAULT_TEAMS: usize = 8;
+const DEFAULT_DAYS: usize = 99; // The postseason starts on day 100
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let command = args[0].as_str();
+    let rest = args[1..].to_vec();
+    let result = match command {
+        "game" => Args::parse(rest, &[&["league", "day", "seed", "save-log", "box-score", "lineup", "rng"][..], &RULE_OPTIONS].concat()).and_then(game),
+        "season" => Args::parse(rest, &[&["teams", "seed", "days", "save", "lineup", "rng"][..], &RULE_OPTIONS].concat()).and_then(season),
+        "gen-player" => Args::parse(rest, &["count", "seed"]).and_then(gen_player),
+        "gen-team" => Args::parse(rest, &["name", "location", "logo", "abbreviation", "favor", "seed"]).and_then(gen_team),
+        "replay" => Args::parse(rest, &["speed"]).and_then(replay),
+        _ => Err(CliError::Usage(format!("unknown command `{}`", command))),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // Whatever was reading the output (like `head`) has all it wants
+        Err(CliError::Io(_, error)) if error.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("albtrs: {}", error);
+            if let CliError::Usage(_) = error {
+                eprintln!("Run `albtrs --help` for usage.");
+            }
+            ExitCode::from(error.exit_code())
+        },
+    }
+}
+
+fn game(args: Args) -> Result<(), CliError> {
+    // Check every option before reading any files, so a bad one is always a usage error
+    let [home, away] = args.positionals("game <home> <away>")?;
+    let day = args.option("day")?.unwrap_or(1);
+    if day == 0 {
+        return Err(CliError::Usage("days start at 1".to_string()));
+    }
+    let rules = args.rules(Rules::default())?;
+    let strategy = args.lineup_strategy()?;
+    let seed: Option<i64> = args.option("seed")?;
+    let rng = args.rng_kind()?.unwrap_or_default();
+    let format = args.format()?;
+
+    let (mut home, mut away) = match args.get("league") {
+        Some(path) => {
+            let league = load_league(Path::new(path))?;
+            (find_team(&league, home)?, find_team(&league, away)?)
+        },
+        None => (read_json::<Team>(Path::new(home))?, read_json::<Team>(Path::new(away))?),
+    };
+    if let Some(strategy) = strategy {
+        home.set_lineup_strategy(strategy);
+        away.set_lineup_strategy(strategy);
+    }
+    let seed = seed.unwrap_or_else(|| Game::seed_for(day, &home, &away));
+    let mut game = Game::with_rng_kind(home, away, day, 0, rules, rng, seed);
+
+    let mut out = Output::new(format);
+    let mut printed = 0;
+    while !game.is_over() {
+        let events = game.step();
+        match out.format {
+            Format::Text => {
+                for message in game.message_log.messages.range(printed..) {
+                    out.line(&message.message_line())?;
+                }
+                printed = game.message_log.messages.len();
+            },
+            Format::Json => {
+                for event in events.iter() {
+                    out.json(event)?;
+                }
+            },
+        }
+    }
+
+    if let Some(path) = args.get("save-log") {
+        write_json(Path::new(path), &game.message_log)?;
+    }
+    if let Some(path) = args.get("box-score") {
+        let box_score = game.box_score();
+        match out.format {
+            Format::Text => fs::write(path, box_score.to_string()).map_err(|error| CliError::Io(PathBuf::from(path), error))?,
+            Format::Json => write_json(Path::new(path), &box_score)?,
+        }
+    }
+    Ok(())
+}
+
+fn season(args: Args) -> Result<(), CliError> {
+    // Check every option before reading any files, so a bad one is always a usage error
+    let path = args.optional_positional("season [<league>]")?;
+    let seed: Option<i64> = args.option("seed")?;
+    let teams = args.option("teams")?.unwrap_or(DEFAULT_TEAMS);
+    if teams < 2 {
+        return Err(CliError::Usage("--teams has to be at least 2".to_string()));
+    }
+    // Just the options for now, they go on top of the league's rules once it's loaded
+    args.rules(Rules::default())?;
+    let strategy = args.lineup_strategy()?;
+    let rng = args.rng_kind()?;
+    let days = args.option("days")?.unwrap_or(DEFAULT_DAYS);
+    let format = args.format()?;
+
+    let mut league = match path {
+        Some(path) => load_league(Path::new(path))?,
+        None => {
+            let mut generator = TeamGenerator::new(Seed::new(seed.unwrap_or(0)).subsystem(Subsystem::Teams).value());
+            let mut league = League::empty();
+            for _ in 0..teams {
+                league.add_team(generator.next_team()).map_err(|error| CliError::Invalid(error.to_string()))?;
+            }
+            league
+        },
+    };
+    if league.teams.len() < 2 {
+        return Err(CliError::Invalid("a season needs at least two teams".to_string()));
+    }
+    league.rules = args.rules(league.rules)?;
+    if let Some(strategy) = strategy {
+        for team in league.teams.iter_mut() {
+            team.set_lineup_strategy(strategy);
+        }
+    }
+
+    if let Some(seed) = seed {
+        league.seed = seed;
+    }
+    if let Some(kind) = rng {
+        league.rng = kind;
+    }
+
+    // Every season starts from scratch
+    league.season += 1;
+    league.clear_statistics();
+    league.expire_modifiers(ModifierDuration::Season);
+    for team in league.teams.iter_mut() {
+        team.set_record(0, 0, 0);
+    }
+    league.generate_schedule(days);
+
+    let mut out = Output::new(format);
+    while let Some(day) = league.games.next_day() {
+        for game in league.play_day(day) {
+            let (home_score, away_score) = game.scores;
+            match out.format {
+                Format::Text => out.line(&format!(
+                    "Day {}: {} {} @ {} {}",
+                    day,
+                    game.away.get_name(),
+                    away_score,
+                    game.home.get_name(),
+                    home_score,
+                ))?,
+                Format::Json => out.json(&json!({
+                    "type": "game",
+                    "day": day,
+                    "home": game.home.get_name(),
+                    "away": game.away.get_name(),
+                    "home_score": home_score,
+                    "away_score": away_score,
+                    "weather": game.weather.name(),
+                }))?,
+            }
+        }
+    }
+
+    if out.format == Format::Text {
+        out.line("")?;
+        out.line("Standings")?;
+    }
+    for (rank, team) in league.standings().into_iter().enumerate() {
+        match out.format {
+            Format::Text => out.line(&format!(
+                "{:>3}. {:<32} {}, {}",
+                rank + 1,
+                team.get_team_name(),
+                team.get_readable_wins(),
+                team.get_readable_losses(),
+            ))?,
+            Format::Json => out.json(&json!({
+                "type": "standing",
+                "rank": rank + 1,
+                "team": team.get_team_name(),
+                "wins": team.get_wins(),
+                "losses": team.get_losses(),
+            }))?,
+        }
+    }
+
+    if let Some(path) = args.get("save") {
+        write_json(Path::new(path), &league)?;
+    }
+    Ok(())
+}
+
+fn gen_player(args: Args) -> Result<(), CliError> {
+    args.positionals::<0>("gen-player")?;
+    let mut rng = Random::new(args.option("seed")?.unwrap_or_else(time_seed));
+    let mut out = Output::new(args.format()?);
+    for _ in 0..args.option("count")?.unwrap_or(1) {
+        let player = Player::default(&mut rng);
+        match out.format {
+            Format::Text => out.line(&describe_player(&player))?,
+            Format::Json => out.json(&player)?,
+        }
+    }
+    Ok(())
+}
+
+fn gen_team(args: Args) -> Result<(), CliError> {
+    args.positionals::<0>("gen-team")?;
+    let mut generator = TeamGenerator::new(args.option("seed")?.unwrap_or_else(time_seed));
+    let mut team = generator.next_team();
+    if let Some(location) = args.get("location") {
+        team.location = location.to_string();
+        team.abbreviation = generator.abbreviate(location);
+    }
+    if let Some(name) = args.get("name") {
+        team.name = name.to_string();
+    }
+    if let Some(logo) = args.get("logo") {
+        team.logo = logo.to_string();
+    }
+    if let Some(abbreviation) = args.get("abbreviation") {
+        team.abbreviation = abbreviation.to_string();
+    }
+    if let Some(favor) = args.option("favor")? {
+        team.set_favor(favor);
+    }
+
+    let mut out = Output::new(args.format()?);
+    match out.format {
+        Format::Text => {
+            out.line(&format!("{} {} ({})", team.logo, team.get_team_name(), team.abbreviation))?;
+            out.line("Lineup")?;
+            for player in team.get_active_batters().iter() {
+                out.line(&format!("  {}", describe_player(player)))?;
+            }
+            out.line("Rotation")?;
+            for player in team.get_active_pitchers().iter() {
+                out.line(&format!("  {}", describe_player(player)))?;
+            }
+        },
+        Format::Json => out.json(&team)?,
+    }
+    Ok(())
+}
+
+fn replay(args: Args) -> Result<(), CliError> {
+    let [path] = args.positionals("replay <log>")?;
+    let mut log: MessageLog = read_json(Path::new(path))?;
+    let speed = match args.get("speed") {
+        Some(speed) => parse_speed(speed)?,
+        None => Speed::default(),
+    };
+
+    let clock = SystemClock;
+    let start = log.peek().map_or(0, |(_, time, _)| *time);
+    let mut playback = Playback::new(start);
+    playback.extend(&mut log);
+    playback.set_speed(speed, &clock);
+    playback.play(&clock);
+
+    let mut out = Output::new(args.format()?);
+    while !playback.is_finished() {
+        for message in playback.poll(&clock) {
+            match out.format {
+                Format::Text => out.line(&message.message_line())?,
+                Format::Json => out.json(&json!({ "message": message.message_line() }))?,
+            }
+        }
+        if let Some(time) = playback.wake_at(&clock) {
+            let wait = time.saturating_sub(clock.now());
+            thread::sleep(Duration::from_millis(wait.try_into().unwrap_or(u64::MAX)));
+        }
+    }
+    Ok(())
+}
+
+fn describe_player(player: &Player) -> String {
+    format!(
+        "{:<24} batting {:.2}  pitching {:.2}  baserunning {:.2}  defense {:.2}",
+        player.get_name(),
+        player.get_batting(),
+        player.get_pitching(),
+        player.get_baserunning(),
+        player.get_defense(),
+    )
+}
+
+fn time_seed() -> i64 {
+    SystemClock.now() as i64
+}
+
+fn parse_speed(speed: &str) -> Result<Speed, CliError> {
+    if speed == "instant" {
+        return Ok(Speed::Instant);
+    }
+    match speed.strip_suffix('x').unwrap_or(speed).parse() {
+        Ok(times) if times > 0 => Ok(Speed::Times(times)),
+        _ => Err(CliError::Usage(format!("invalid speed `{}`, expected something like 1x, 10x or instant", speed))),
+    }
+}
+
+fn find_team(league: &League, name: &str) -> Result<Team, CliError> {
+    league.find_by_name(name).cloned().ok_or_else(|| CliError::Invalid(format!("no team named `{}` in the league", name)))
+}
+
+/// Loads a Java league save directory, or a league saved as JSON.
+fn load_league(path: &Path) -> Result<League, CliError> {
+    if path.is_dir() {
+        importer::load_league(path, &mut Random::new(0)).map_err(CliError::from)
+    } else {
+        read_json(path)
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, CliError> {
+    let text = fs::read_to_string(path).map_err(|error| CliError::Io(path.to_path_buf(), error))?;
+    serde_json::from_str(&text).map_err(|error| CliError::Invalid(format!("{}: {}", path.display(), error)))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), CliError> {
+    let text = serde_json::to_string(value).map_err(|error| CliError::Invalid(format!("{}: {}", path.display(), error)))?;
+    fs::write(path, text).map_err(|error| CliError::Io(path.to_path_buf(), error))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json, // One JSON object per line
+}
+
+struct Output {
+    format: Format,
+    out: io::StdoutLock<'static>,
+}
+
+impl Output {
+    fn new(format: Format) -> Self {
+        Output {
+            format,
+            out: io::stdout().lock(),
+        }
+    }
+
+    fn line(&mut self, line: &str) -> Result<(), CliError> {
+        writeln!(self.out, "{}", line).map_err(|error| CliError::Io(PathBuf::from("<stdout>"), error))
+    }
+
+    fn json<T: Serialize>(&mut self, value: &T) -> Result<(), CliError> {
+        let line = serde_json::to_string(value).map_err(|error| CliError::Invalid(error.to_string()))?;
+        self.line(&line)
+    }
+}
+
+/// A subcommand's arguments: positionals, and `--option value` or `--option=value` pairs.
+struct Args {
+    positionals: Vec<String>,
+    options: BTreeMap<String, String>,
+}
+
+impl Args {
+    // `allowed` doesn't need to include `format`, every command takes it.
+    fn parse(args: Vec<String>, allowed: &[&str]) -> Result<Self, CliError> {
+        let mut parsed = Args {
+            positionals: Vec::new(),
+            options: BTreeMap::new(),
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(option) = arg.strip_prefix("--") else {
+                parsed.positionals.push(arg);
+                continue;
+            };
+            let (name, value) = match option.split_once('=') {
+                Some((name, value)) => (name.to_string(), value.to_string()),
+                None => {
+                    let value = args.next().ok_or_else(|| CliError::Usage(format!("--{} needs a value", option)))?;
+                    (option.to_string(), value)
+                },
+            };
+            if name != "format" && !allowed.contains(&name.as_str()) {
+                return Err(CliError::Usage(format!("unknown option --{}", name)));
+            }
+            parsed.options.insert(name, value);
+        }
+        Ok(parsed)
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn option<T: FromStr>(&self, name: &str) -> Result<Option<T>, CliError> {
+        self.get(name)
+            .map(|value| value.parse().map_err(|_| CliError::Usage(format!("invalid value `{}` for --{}", value, name))))
+            .transpose()
+    }
+
+    /// Returns exactly `N` positionals. `usage` is shown when there are too many or too few.
+    fn positionals<const N: usize>(&self, usage: &str) -> Result<[&str; N], CliError> {
+        let positionals: Vec<&str> = self.positionals.iter().map(String::as_str).collect();
+        positionals.try_into().map_err(|_| CliError::Usage(format!("usage: albtrs {}", usage)))
+    }
+
+    fn optional_positional(&self, usage: &str) -> Result<Option<&str>, CliError> {
+        match self.positionals.as_slice() {
+            [] => Ok(None),
+            [positional] => Ok(Some(positional)),
+            _ => Err(CliError::Usage(format!("usage: albtrs {}", usage))),
+        }
+    }
+
+    /// `rules`, with whatever rule options were given swapped in.
+    fn rules(&self, rules: Rules) -> Result<Rules, CliError> {
+        let rules = Rules {
+            strikes: self.option("strikes")?.unwrap_or(rules.strikes),
+            balls: self.option("balls")?.unwrap_or(rules.balls),
+            outs: self.option("outs")?.unwrap_or(rules.outs),
+            innings: self.option("innings")?.unwrap_or(rules.innings),
+            bases: self.option("bases")?.unwrap_or(rules.bases),
+        };
+        rules.validate().map_err(|error| CliError::Usage(format!("--{}", error)))?;
+        Ok(rules)
+    }
+
+    fn lineup_strategy(&self) -> Result<Option<LineupStrategy>, CliError> {
+        self.get("lineup")
+            .map(|name| {
+                LineupStrategy::from_name(name)
+                    .ok_or_else(|| CliError::Usage(format!("unknown lineup strategy `{}`, expected manual, batting, obp or shuffle", name)))
+            })
+            .transpose()
+    }
+
+    fn rng_kind(&self) -> Result<Option<RngKind>, CliError> {
+        self.get("rng")
+            .map(|name| {
+                RngKind::from_name(name)
+                    .ok_or_else(|| CliError::Usage(format!("unknown RNG `{}`, expected java or counter", name)))
+            })
+            .transpose()
+    }
+
+    fn format(&self) -> Result<Format, CliError> {
+        match self.get("format") {
+            None | Some("text") => Ok(Format::Text),
+            Some("json") => Ok(Format::Json),
+            Some(format) => Err(CliError::Usage(format!("unknown format `{}`, expected text or json", format))),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CliError {
+    Io(PathBuf, io::Error),
+    Usage(String),
+    Invalid(String), // A file was read, but what's in it doesn't make sense
+}
+
+impl CliError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Io(..) => 1,
+            CliError::Usage(_) => 2,
+            CliError::Invalid(_) => 3,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Io(path, error) => write!(f, "couldn't access {}: {}", path.display(), error),
+            CliError::Usage(message) => write!(f, "{}", message),
+            CliError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<ImportError> for CliError {
+    fn from(error: ImportError) -> Self {
+        match error {
+            ImportError::Io(path, error) => CliError::Io(path, error),
+            error => CliError::Invalid(error.to_string()),
+        }
+    }
+}
@@ -1,3 +1,187 @@
-use std::collections::HashMap;
+//! A character-level Markov chain for generating names that sound like the
+//! ones it was trained on.
+//!
+//! The chain looks at the last `order` letters of a name to pick the next
+//! one. Higher orders stick closer to the training names, and lower orders
+//! come up with stranger ones.
+//!
+//! ```
+//! use alt_league_blaseball::java_random::Random;
+//! use alt_league_blaseball::markov_chain::MarkovChain;
+//!
+//! let chain = MarkovChain::bundled(3);
+//! let mut rng = Random::new(5);
+//! let name = chain.generate(&mut rng).unwrap();
+//! assert!(name.chars().next().unwrap().is_uppercase());
+//! ```
 
-//todo: make random name generator markov chain
\ No newline at end of file
+use std::collections::BTreeMap;
+
+use crate::rng::GameRng;
+use crate::name_generator::capitalize;
+
+/// The names the chain is trained on when no other names are given. One name per line.
+pub const BUNDLED_NAMES: &str = include_str!("../data/names.txt");
+
+const START: char = '^'; // Pads the start of every name, so the chain knows how names begin
+const END: char = '$'; // Follows the last letter of every name
+
+const MIN_LENGTH: usize = 3;
+const MAX_LENGTH: usize = 12;
+const ATTEMPTS: usize = 100; // How many names to try before giving up on one that isn't too short, too long or already taken
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkovChain {
+    order: usize,
+    // The last `order` letters, and how many times each letter followed
+    // them. Kept sorted so the same seed always gives the same name.
+    transitions: BTreeMap<String, BTreeMap<char, u32>>,
+    // Everything it was trained on, so it doesn't just hand those back
+    names: Vec<String>,
+}
+
+impl MarkovChain {
+    /// Creates an untrained chain.
+    ///
+    /// # Panics
+    /// If `order` is 0, this function panics.
+    pub fn new(order: usize) -> Self {
+        assert!(order > 0, "a Markov chain needs to look at at least one letter");
+        MarkovChain {
+            order,
+            transitions: BTreeMap::new(),
+            names: Vec::new(),
+        }
+    }
+
+    /// Creates a chain trained on every line of `corpus`. Blank lines are skipped.
+    pub fn from_corpus(order: usize, corpus: &str) -> Self {
+        let mut chain = MarkovChain::new(order);
+        for name in corpus.lines() {
+            chain.train(name);
+        }
+        chain
+    }
+
+    /// Creates a chain trained on the names that come with the crate.
+    pub fn bundled(order: usize) -> Self {
+        MarkovChain::from_corpus(order, BUNDLED_NAMES)
+    }
+
+    pub fn get_order(&self) -> usize {
+        self.order
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Adds a name to what the chain has learned. Case is ignored.
+    pub fn train(&mut self, name: &str) {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            return;
+        }
+
+        let mut state: Vec<char> = vec![START; self.order];
+        for next in name.chars().chain([END]) {
+            let counts = self.transitions.entry(state.iter().collect()).or_default();
+            *counts.entry(next).or_insert(0) += 1;
+            state.remove(0);
+            state.push(next);
+        }
+        self.names.push(name);
+    }
+
+    /// Generates a new name, capitalized. Tries to avoid names that are
+    /// too short, too long, or that it was trained on, but will settle for
+    /// one of those if it can't come up with anything else. Returns `None`
+    /// if the chain hasn't been trained.
+    pub fn generate(&self, rng: &mut impl GameRng) -> Option<String> {
+        self.generate_from(rng, "")
+    }
+
+    /// Works the same as `generate()`, but every name starts with `prefix`.
+    pub fn generate_from(&self, rng: &mut impl GameRng, prefix: &str) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut name = String::new();
+        for _ in 0..ATTEMPTS {
+            name = self.walk(rng, &prefix.to_lowercase());
+            let length = name.chars().count();
+            if (MIN_LENGTH..=MAX_LENGTH).contains(&length) && !self.names.contains(&name) {
+                break;
+            }
+        }
+        if name.is_empty() {
+            return None;
+        }
+        Some(capitalize(&name))
+    }
+
+    // Follows the chain from `prefix` until it reaches the
+    // end of a name, or a state it's never seen.
+    fn walk(&self, rng: &mut impl GameRng, prefix: &str) -> String {
+        let mut name = prefix.to_string();
+        let mut state: Vec<char> = vec![START; self.order];
+        for c in prefix.chars() {
+            state.remove(0);
+            state.push(c);
+        }
+
+        while name.chars().count() < MAX_LENGTH * 2 {
+            let Some(counts) = self.transitions.get(&state.iter().collect::<String>()) else {
+                break;
+            };
+            let next = pick(rng, counts);
+            if next == END {
+                break;
+            }
+            name.push(next);
+            state.remove(0);
+            state.push(next);
+        }
+        name
+    }
+}
+
+// Picks a letter with odds in proportion to how many
+// times it was seen.
+fn pick(rng: &mut impl GameRng, counts: &BTreeMap<char, u32>) -> char {
+    let total: u32 = counts.values().sum();
+    let mut roll = rng.next_u32_bound(total);
+    for (c, count) in counts {
+        if roll < *count {
+            return *c;
+        }
+        roll -= count;
+    }
+    END
+}
+
+#[test]
+fn test_markov_chain() {
+    use crate::java_random::Random;
+    use crate::name_generator::{Generator, NamingSystem};
+
+    let chain = MarkovChain::from_corpus(2, "Anna\nHannah\n\nAnnabel\n");
+    assert_eq!(chain.transitions["^^"], BTreeMap::from([('a', 2), ('h', 1)]));
+    assert_eq!(chain.transitions["nn"], BTreeMap::from([('a', 3)]));
+    assert!(MarkovChain::new(3).generate(&mut Random::new(1)).is_none());
+
+    // Same seed, same names
+    let chain = MarkovChain::bundled(3);
+    let names: Vec<String> = (0..20).map(|seed| chain.generate(&mut Random::new(seed)).unwrap()).collect();
+    let again: Vec<String> = (0..20).map(|seed| chain.generate(&mut Random::new(seed)).unwrap()).collect();
+    assert_eq!(names, again);
+    for name in names.iter() {
+        assert!((MIN_LENGTH..=MAX_LENGTH).contains(&name.chars().count()), "{}", name);
+        assert!(!BUNDLED_NAMES.lines().any(|line| line == name), "{}", name);
+    }
+    assert!(chain.generate_from(&mut Random::new(2), "Qu").unwrap().starts_with("Qu"));
+
+    let mut generator = Generator::with_system(0, NamingSystem::Markov(chain));
+    assert_eq!(generator.next_name(), names[0]);
+}
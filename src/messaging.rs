@@ -1,22 +1,28 @@
-/// This handles all the messaging between the game and the user.
-/// This is directly inspired by RustRoguelike's messaging system, which you can find here:
-/// https://github.com/nsmryan/RustRoguelike/blob/master/roguelike_core/src/messaging.rs
+//! This handles all the messaging between the game and the user.
+//! This is directly inspired by RustRoguelike's messaging system, which you can find here:
+//! https://github.com/nsmryan/RustRoguelike/blob/master/roguelike_core/src/messaging.rs
 
 use std::collections::VecDeque;
 
+use crate::event::{Event, EventKind, PlayerId, TeamId};
+use crate::game::Game;
+use crate::player_flavor::PregameRitual;
+use crate::weather::Bird;
+
 pub type BallsStrikes = (i32, i32);
 
-// Also derives Copy, Deserialize, and Serialize
+// Also derives Copy in the original
 // Copy doesn't work here because we store player names instead of ids to players, whose names are retrieved from the database.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Message {
     // Game event messages
     AnnounceMatchup(String, String), // Team 1, Team 2
     StartGame,
     InningStart(bool, u32, String, String), // Top, Inning, Team1, Team2
     CurrentScore(String, f64, f64, String), // Team1Abbr, Team1Score, Team2Score, Team2Abbr
-    Steal(String, usize), // Stealer Name, Base Stolen
-    CaughtStealing(String, usize), // Stealer Name, Base Stolen
+    Steal(String, Option<usize>), // Stealer Name, Base Stolen (None for home)
+    CaughtStealing(String, Option<usize>), // Stealer Name, Base Stolen (None for home)
     Walk(String), // Batter Name
     Ball(BallsStrikes), // BallsStrikes
     StruckOutLooking(String, BallsStrikes), // Batter Name, Balls Strikes
@@ -28,7 +34,7 @@ pub enum Message {
     Groundout(String, String), // Batter Name, Defender Name
     // NOTE: Maybe I should have Bases Hit be its own enum?
     Scores(String), // Batter Name
-    Hit(String, usize), // Batter Name, Bases Hit
+    Hit(String, Option<usize>), // Batter Name, Bases Hit (None for a home run)
     // Figure something out for the format_balls_strikes() function
     NextBatter(String, String), // Batter Name, Team Name
     Out(i32), // Number of outs
@@ -38,9 +44,65 @@ pub enum Message {
 
     // Weather specific messages
     Crabs,
+    Incinerated(String, String), // Victim Name, Replacement Name
+    Birds(Bird, Option<(String, PregameRitual)>), // Bird, Player Name and Ritual
+
+    // Modifier specific messages
+    FireEater(String), // Player Name
+    Flicker(String), // Runner Name
+    Reverberating(String), // Batter Name
 }
 
 impl Message {
+    /// Renders an `Event` into the `Message` shown to the user. Player and
+    /// team names are looked up in the `Game` the event came from.
+    ///
+    /// Returns `None` for events that don't get their own line, like
+    /// baserunners advancing.
+    pub fn from_event(event: &Event, game: &Game) -> Option<Message> {
+        let player_name = |id: PlayerId| game.find_player(id).map(|player| player.get_name()).unwrap_or_default();
+        let team_name = |id: TeamId| game.find_team(id).map(|team| team.get_name()).unwrap_or_default();
+        // Bases count from 0 in events, and from first base in messages
+        let stolen_base = |from: usize| Some(from + 2).filter(|base| *base <= game.rules.bases);
+
+        let message = match &event.kind {
+            EventKind::StartGame => Message::StartGame,
+            EventKind::InningStart => Message::InningStart(event.top, event.inning, team_name(event.batting_team), team_name(event.pitching_team)),
+            EventKind::NextBatter => Message::NextBatter(player_name(event.batter), team_name(event.batting_team)),
+            EventKind::Ball => Message::Ball(event.count_after),
+            EventKind::Walk => Message::Walk(player_name(event.batter)),
+            EventKind::StrikeLooking => Message::StrikeLooking(event.count_after),
+            EventKind::StruckOutLooking => Message::StruckOutLooking(player_name(event.batter), event.count_after),
+            EventKind::StrikeSwinging => Message::StrikeSwinging(event.count_after),
+            EventKind::StruckOutSwinging => Message::StruckOutSwinging(player_name(event.batter), event.count_after),
+            EventKind::FoulBall => Message::FoulBall(event.count_after),
+            EventKind::Flyout { defender } => Message::Flyout(player_name(event.batter), player_name(*defender)),
+            EventKind::Groundout { defender } => Message::Groundout(player_name(event.batter), player_name(*defender)),
+            EventKind::Hit { bases } => Message::Hit(player_name(event.batter), Some(*bases).filter(|bases| *bases <= game.rules.bases)),
+            EventKind::Steal { runner, base } => Message::Steal(player_name(*runner), stolen_base(*base)),
+            EventKind::CaughtStealing { runner, base } => Message::CaughtStealing(player_name(*runner), stolen_base(*base)),
+            EventKind::Advance { .. } => return None,
+            EventKind::Score { runner } => Message::Scores(player_name(*runner)),
+            EventKind::Out => Message::Out(event.outs),
+            EventKind::InningToOuting => Message::InningToOuting(event.inning),
+            EventKind::GameOver { .. } => Message::GameOver,
+
+            EventKind::Crabs => Message::Crabs,
+            EventKind::Incineration { victim, replacement } => Message::Incinerated(player_name(*victim), player_name(*replacement)),
+            EventKind::Birds { bird } => {
+                let player = bird.player
+                    .and_then(|id| game.find_player(id))
+                    .map(|player| (player.get_name(), player.get_ritual().clone()));
+                Message::Birds(*bird, player)
+            },
+
+            EventKind::FireEater { player } => Message::FireEater(player_name(*player)),
+            EventKind::Flicker { runner } => Message::Flicker(player_name(*runner)),
+            EventKind::Reverberating { player } => Message::Reverberating(player_name(*player)),
+        };
+        Some(message)
+    }
+
     pub fn message_line(&self) -> String {
         match self {
             Message::AnnounceMatchup(team1, team2) => {
@@ -63,42 +125,10 @@ impl Message {
                 format!("[Current score is {} {}-{} {}]", team1, Self::score_as_string(score1), Self::score_as_string(score2), team2)
             },
             Message::Steal(stealer, base) => {
-                let stolen_base: String;
-                match base {
-                    0 => {
-                        stolen_base = "second base".to_string();
-                    },
-                    1 => {
-                        stolen_base = "third base".to_string();
-                    },
-                    2 => {
-                        stolen_base = "home".to_string();
-                    },
-                    _ => {
-                        // Fourth base isn't out yet
-                        panic!("Invalid base number: {}", base);
-                    }
-                }
-                format!("{} steals {}!", stealer, stolen_base)
+                format!("{} steals {}!", stealer, Self::base_name(*base))
             },
             Message::CaughtStealing(stealer, base) => {
-                let stolen_base: String;
-                match base {
-                    0 => {
-                        stolen_base = "second base".to_string();
-                    },
-                    1 => {
-                        stolen_base = "third base".to_string();
-                    },
-                    2 => {
-                        stolen_base = "home".to_string();
-                    },
-                    _ => {
-                        // Fourth base isn't out yet
-                        panic!("Invalid base number: {}", base);
-                    }
-                }
-                format!("{} gets caught stealing {}.", stealer, stolen_base)
+                format!("{} gets caught stealing {}.", stealer, Self::base_name(*base))
             },
             Message::Walk(batter) => {
                 format!("{} draws a walk.", batter)
@@ -132,22 +162,15 @@ impl Message {
             },
             Message::Hit(batter, bases_hit) => {
                 // let mut message = format!("{} his a ", batter);
-                let base: String;
-                match bases_hit {
-                    1 => {
-                        base = "Single".to_string();
-                    },
-                    2 => {
-                        base = "Double".to_string();
-                    },
-                    3 => {
-                        base = "Triple".to_string();
-                    },
-                    // Eventually this might need to support the fourth base, if that's ever added
-                    _ => {
-                        base = "Home Run".to_string();
-                    }
-                }
+                let base = match bases_hit {
+                    Some(1) => "Single".to_string(),
+                    Some(2) => "Double".to_string(),
+                    Some(3) => "Triple".to_string(),
+                    Some(4) => "Quadruple".to_string(),
+                    Some(5) => "Quintuple".to_string(),
+                    Some(bases) => format!("{}-Base Hit", bases),
+                    None => "Home Run".to_string(),
+                };
                 format!("{} hits a {}!", batter, base)
             },
             Message::NextBatter(batter, team) => {
@@ -171,25 +194,50 @@ impl Message {
             Message::Crabs => {
                 "Crabs fill the field.".to_string()
             }
+            Message::Incinerated(victim, replacement) => {
+                format!("A rogue umpire incinerated {}!\nThey're replaced by {}.", victim, replacement)
+            }
+            Message::Birds(bird, player) => {
+                bird.message_line(player.as_ref().map(|(name, ritual)| (name.as_str(), ritual)))
+            }
+
+            Message::FireEater(player) => {
+                format!("A rogue umpire tries to incinerate {}, but they eat the fire!", player)
+            }
+            Message::Flicker(runner) => {
+                format!("{} flickers away from the tag!", runner)
+            }
+            Message::Reverberating(batter) => {
+                format!("{} is Reverberating wildly!", batter)
+            }
         }
     }
 
     fn format_balls_strikes((balls, strikes): &BallsStrikes) -> String {
-        let b: String;
-        let s: String;
-        if balls < &0 {
-            b = format!("({})", balls.abs());
+        let b = if balls < &0 {
+            format!("({})", balls.abs())
         } else {
-            b = format!("{}", balls);
-        }
-        if strikes < &0 {
-            s = format!("({})", strikes.abs());
+            format!("{}", balls)
+        };
+        let s = if strikes < &0 {
+            format!("({})", strikes.abs())
         } else {
-            s = format!("{}", strikes);
-        }
+            format!("{}", strikes)
+        };
         format!("{}-{}", b, s)
     }
 
+    fn base_name(base: Option<usize>) -> String {
+        const NAMES: [&str; 9] = ["first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth"];
+        match base {
+            Some(base) => match NAMES.get(base.wrapping_sub(1)) {
+                Some(name) => format!("{} base", name),
+                None => format!("base {}", base),
+            },
+            None => "home".to_string(),
+        }
+    }
+
     fn score_as_string(score: &f64) -> String {
         let mut message = String::new();
         if score % 1.0 == 0.0 {
@@ -204,14 +252,20 @@ impl Message {
     }
 }
 
-// Also includes Serialize and Deserialize in the original
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MessageLog {
     pub messages: VecDeque<Message>,
     pub time: VecDeque<u128>,
     pub is_special: VecDeque<bool>,
 }
 
+impl Default for MessageLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MessageLog {
     pub fn new() -> Self {
         MessageLog {
@@ -221,6 +275,10 @@ impl MessageLog {
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
     pub fn len(&self) -> Option<usize> {
         if self.messages.len() != self.time.len() && self.messages.len() != self.is_special.len() {
             None
@@ -230,6 +288,7 @@ impl MessageLog {
     }
 
     pub fn pop_front(&mut self) -> Option<(Message, u128)> {
+        self.is_special.pop_front();
         self.messages.pop_front().and_then(|message| {
             self.time.pop_front().map(|time| (message, time))
         })
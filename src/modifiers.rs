@@ -0,0 +1,137 @@
+//! Modifiers (mods) are named effects attached to a `Player` or a `Team`.
+//! A team's modifiers apply to everyone on it.
+//!
+//! The engine never checks for a specific modifier. At each hook point it
+//! asks every modifier in play what should happen instead, so adding a
+//! modifier only takes a new `ModifierKind` and the hooks it cares about.
+
+use crate::rng::GameRng;
+use crate::player::Player;
+
+const FIRE_EATER_BOOST: f64 = 0.5; // Added to every stat category when a Fire Eater eats the fire
+const FLICKER_CHANCE: f64 = 0.5;
+const REVERBERATE_CHANCE: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierKind {
+  FourthStrike,
+  FireEater,
+  Shelled,
+  Flickering,
+  Reverberating,
+}
+
+/// How long a modifier lasts. Modifiers that last an inning are gone at the end of the half inning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierDuration {
+  Inning,
+  Game,
+  Season,
+  Permanent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Modifier {
+  pub kind: ModifierKind,
+  pub duration: ModifierDuration,
+}
+
+impl Modifier {
+  pub fn new(kind: ModifierKind, duration: ModifierDuration) -> Self {
+    Modifier {
+      kind,
+      duration,
+    }
+  }
+
+  pub fn permanent(kind: ModifierKind) -> Self {
+    Modifier::new(kind, ModifierDuration::Permanent)
+  }
+
+  /// Whether the modifier is still around once something that lasts `duration` is over.
+  pub fn outlasts(&self, duration: ModifierDuration) -> bool {
+    self.duration > duration
+  }
+}
+
+impl ModifierKind {
+  pub fn name(&self) -> &'static str {
+    match self {
+      ModifierKind::FourthStrike => "Fourth Strike",
+      ModifierKind::FireEater => "Fire Eater",
+      ModifierKind::Shelled => "Shelled",
+      ModifierKind::Flickering => "Flickering",
+      ModifierKind::Reverberating => "Reverberating",
+    }
+  }
+
+  pub fn description(&self) -> &'static str {
+    match self {
+      ModifierKind::FourthStrike => "Batters need four strikes to strike out.",
+      ModifierKind::FireEater => "Rogue umpires can't incinerate this player. They eat the fire and get a boost instead.",
+      ModifierKind::Shelled => "Can't bat or pitch.",
+      ModifierKind::Flickering => "Sometimes flickers away from the tag when caught stealing.",
+      ModifierKind::Reverberating => "Sometimes bats again right after a plate appearance.",
+    }
+  }
+
+  // Hooks. Each one is handed what the engine would do
+  // without the modifier and returns what it should do.
+
+  /// Checked in `do_pitch`: how many strikes it takes to strike the batter out.
+  pub fn strikes(&self, strikes: i32) -> i32 {
+    match self {
+      ModifierKind::FourthStrike => strikes + 1,
+      _ => strikes,
+    }
+  }
+
+  /// Checked before a walk: how many balls it takes to walk the batter.
+  pub fn balls(&self, balls: i32) -> i32 {
+    balls
+  }
+
+  /// Whether the player can step up to the plate or onto the mound.
+  pub fn can_play(&self) -> bool {
+    !matches!(self, ModifierKind::Shelled)
+  }
+
+  /// Checked in `steal_attempt` when a runner is caught: whether they get away with it anyway.
+  pub fn escapes_tag(&self, rng: &mut impl GameRng) -> bool {
+    match self {
+      ModifierKind::Flickering => rng.next_f64() < FLICKER_CHANCE,
+      _ => false,
+    }
+  }
+
+  /// Checked in `score`: how much the runner's run is worth.
+  pub fn run_value(&self, value: f64) -> f64 {
+    value
+  }
+
+  /// Checked before an incineration: whether the player survives it.
+  /// The player can be changed by the attempt.
+  pub fn resists_incineration(&self, player: &mut Player) -> bool {
+    match self {
+      ModifierKind::FireEater => {
+        player.boost_batting_by(FIRE_EATER_BOOST);
+        player.boost_pitching_by(FIRE_EATER_BOOST);
+        player.boost_baserunning_by(FIRE_EATER_BOOST);
+        player.boost_defense_by(FIRE_EATER_BOOST);
+        true
+      },
+      _ => false,
+    }
+  }
+
+  /// Checked when a plate appearance is over: whether the batter steps up again.
+  pub fn bats_again(&self, rng: &mut impl GameRng) -> bool {
+    match self {
+      ModifierKind::Reverberating => rng.next_f64() < REVERBERATE_CHANCE,
+      _ => false,
+    }
+  }
+}
@@ -1,7 +1,8 @@
-// todo: make random name generator
-// I think it'd be nice to make this a markov chain at a later point in time, but for now it'll just be the regular name generator.
+// The regular name generator, plus a switch over to the Markov chain one.
 
 use crate::java_random::Random;
+use crate::rng::{GameRng, RngBackend};
+use crate::markov_chain::MarkovChain;
 
 const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
 const CONSONANTS: &str = "bcdfghjklmnpqrstvwxyz";
@@ -23,37 +24,58 @@ enum ConstTypes {
 /// The system used to generate the names. Standard will use
 /// Mixed's default regular name generator, while Markov
 /// will use an experimental Markov chain to generate names. 
+#[derive(Debug, Clone, Default)]
 pub enum NamingSystem {
     /// This represents the default name generator for Alt
     /// League Blaseball. This is laid out by TheMixedMaster
     /// on the RandomNameGenerator file for the game, located at:
     /// 
     /// https://github.com/themixedmaster/alt-league-blaseball/blob/dev/RandomNameGenerator.java
+    #[default]
     Standard,
     /// This represents the experimental Markov chain name generator.
-    Markov,
+    /// Names are generated from whatever the chain was trained on.
+    Markov(MarkovChain),
 }
 
-impl Default for NamingSystem {
-    fn default() -> Self {
-        NamingSystem::Standard
+impl NamingSystem {
+    /// A Markov chain of the given order, trained on the bundled names.
+    pub fn markov(order: usize) -> Self {
+        NamingSystem::Markov(MarkovChain::bundled(order))
     }
 }
 
+
 /// A random name generator.
 pub struct Generator {
-    rng: Random,
+    rng: RngBackend,
     system: NamingSystem,
 }
 
 impl Generator {
     pub fn new(seed: i64) -> Self {
+        Generator::with_system(seed, NamingSystem::default())
+    }
+
+    /// Creates a generator that uses `system` for `next_name()`,
+    /// `next_name_with_distribution()`, `with_starting_letter()` and
+    /// `name_from()`. Everything else always uses the standard algorithm.
+    pub fn with_system(seed: i64, system: NamingSystem) -> Self {
+        Generator::with_rng(RngBackend::Java(Random::new(seed)), system)
+    }
+
+    /// Works the same as `with_system()`, but rolls with `rng`.
+    pub fn with_rng(rng: RngBackend, system: NamingSystem) -> Self {
         Generator {
-            rng: Random::new(seed),
-            system: NamingSystem::default(),
+            rng,
+            system,
         }
     }
+
     pub fn next_name(&mut self) -> String {
+        if let Some(name) = self.markov_name("") {
+            return name;
+        }
         let len = self.random(1, 5);
         let len = len + self.random(1, 5);
         let nth = LETTERS.chars().nth(self.random(1, LETTERS.len())).unwrap();
@@ -69,6 +91,9 @@ impl Generator {
     }
 
     pub fn with_starting_letter(&mut self, starting_letter: char) -> String {
+        if let Some(name) = self.markov_name(&starting_letter.to_string()) {
+            return name;
+        }
         let length = self.random(1, 5) + self.random(1, 5);
         self.generate_name(
             length, 
@@ -77,6 +102,9 @@ impl Generator {
     }
 
     pub fn name_from(&mut self, starting_letters: String) -> String {
+        if let Some(name) = self.markov_name(&starting_letters) {
+            return name;
+        }
         let length = self.random(1, 5) + self.random(1, 5);
         self.generate_name(
             length, 
@@ -87,7 +115,7 @@ impl Generator {
     pub fn generate_name(&mut self, length: usize, starting_letters: String) -> String {
         let mut name = starting_letters.clone();
         let mut l = length;
-        if starting_letters.len() == 0 {
+        if starting_letters.is_empty() {
             name = LETTERS.chars()
                 .nth(self.random(
                     0, 
@@ -108,8 +136,7 @@ impl Generator {
         name = capitalize(&name);
 
         for i in x..l {
-            let mut add = 'a';
-            add = match mode {
+            let add = match mode {
                 0 => {
                     let mut a = VOWELS.chars()
                         .nth(self.random(0, 4))
@@ -159,6 +186,9 @@ impl Generator {
     }
 
     pub fn next_name_with_distribution(&mut self) -> String {
+        if let Some(name) = self.markov_name("") {
+            return name;
+        }
         let len = self.random(1, 4) + self.random(1, 4);
         self.generate_name_with_distribution(len, "".to_string()
         )
@@ -171,7 +201,7 @@ impl Generator {
     pub fn generate_name_with_distribution(&mut self, length: usize, starting_letters: String) -> String {
         let mut n = starting_letters.clone();
         let mut l = length;
-        if starting_letters.len() == 0 {
+        if starting_letters.is_empty() {
             n = self.get_random_letter(ConstTypes::Letters).to_string();
         }
         while l <= n.len() {
@@ -185,7 +215,7 @@ impl Generator {
         let mut mode = self.get_mode(&n, l);
         let mut name = n.clone();
         for i in n.len()..l {
-            let mut add = 'a';
+            let mut add;
             match mode {
                 0 => {
                     add = self.get_random_letter(ConstTypes::VowelsMinusY);
@@ -194,15 +224,13 @@ impl Generator {
                     }
                     mode += 1;
                 }
-                1 | 2 | 3 => {
+                1..=3 => {
                     loop {
                         add = self.get_random_letter(ConstTypes::VowelsMinusY);
                         mode = self.random(4, 7);
                         
-                        if {
-                            !(add == name.chars().nth(name.len() - 1).unwrap() &&
-                            ( add == 'a' || add == 'i' || add == 'u'))
-                        } {
+                        if !(add == name.chars().nth(name.len() - 1).unwrap() &&
+                        ( add == 'a' || add == 'i' || add == 'u')) {
                             break;
                         }
                     }
@@ -215,7 +243,7 @@ impl Generator {
                         mode += 1;
                     }
                 }
-                5 | 6 | 7 => {
+                5..=7 => {
                     add = self.get_random_letter(ConstTypes::Consonants);
                     mode = self.random(0, 3);
                 }
@@ -235,16 +263,16 @@ impl Generator {
         self.fill_middle_with_add(start, end, rand)
     }
 
-    pub fn fill_middle_with_add(&mut self, start: &str, end: &str, add: usize) -> String {
+    pub fn fill_middle_with_add(&mut self, start: &str, _end: &str, add: usize) -> String {
         let mut name = start.to_string();
-        for i in 0..add {
+        for _i in 0..add {
             name.push('-');
         }
         self.unscatter(&name)
     }
 
     fn unscatter(&mut self, name: &str) -> String {
-        let mut name = name.to_string().to_lowercase();
+        let name = name.to_string().to_lowercase();
         let mut simple_name = self.simplify(&name.clone());
         while !is_readable(&simple_name) {
             simple_name = self.simplify(&name.clone());
@@ -253,11 +281,7 @@ impl Generator {
         for (i, c) in name.chars().enumerate() {
             if c == '-' {
                 if simple_name.chars().nth(i).unwrap() == 'v' {
-                    if i == name.len() - 1 {
-                        output.push(VOWELS.chars().nth(self.random(0, 2)).unwrap());
-                    } else {
-                        output.push(VOWELS.chars().nth(self.random(0, 2)).unwrap());
-                    }
+                    output.push(VOWELS.chars().nth(self.random(0, 2)).unwrap());
                 } else {
                     output.push(CONSONANTS.chars().nth(self.random(0, CONSONANTS.len() - 1)).unwrap());
                 }
@@ -272,10 +296,7 @@ impl Generator {
         let mut output = String::new();
         for (i, c) in name.chars().enumerate() {
             if c == '-' {
-                if {
-                    let r = self.random(0, 1);
-                    r == 0
-                } {
+                if self.random(0, 1) == 0 {
                     output.push('v');
                 } else {
                     output.push('c');
@@ -289,12 +310,21 @@ impl Generator {
         output
     }
 
-    pub fn get_seed(&self) -> i64 {
-        self.rng.get_seed()
+    pub fn get_system(&self) -> &NamingSystem {
+        &self.system
     }
 
-    fn split(name: &str) -> Vec<&str> {
-        name.split_whitespace().collect()
+    pub fn set_system(&mut self, system: NamingSystem) {
+        self.system = system;
+    }
+
+    // Generates a name with the Markov chain, if that's the
+    // system in use and it's been trained on anything.
+    fn markov_name(&mut self, starting_letters: &str) -> Option<String> {
+        match &self.system {
+            NamingSystem::Standard => None,
+            NamingSystem::Markov(chain) => chain.generate_from(&mut self.rng, starting_letters),
+        }
     }
 
     fn random(&mut self, min: usize, max: usize) -> usize {
@@ -320,7 +350,7 @@ impl Generator {
         let r = self.rng.next_f64() * array_total(&distribution);
         let mut total = 0.0;
         for (i, val) in distribution.into_iter().enumerate() {
-            total = total + val;
+            total += val;
             if total >= r {
                 match types {
                     ConstTypes::Letters => {
@@ -340,16 +370,16 @@ impl Generator {
         }
         match types {
             ConstTypes::Letters => {
-                return LETTERS.chars().nth(0).unwrap();
+                LETTERS.chars().nth(0).unwrap()
             }
             ConstTypes::Vowels => {
-                return VOWELS.chars().nth(0).unwrap();
+                VOWELS.chars().nth(0).unwrap()
             }
             ConstTypes::Consonants => {
-                return CONSONANTS.chars().nth(0).unwrap();
+                CONSONANTS.chars().nth(0).unwrap()
             }
             ConstTypes::VowelsMinusY => {
-                return VOWELS_MINUS_Y.chars().nth(0).unwrap();
+                VOWELS_MINUS_Y.chars().nth(0).unwrap()
             }
         }
     }
@@ -390,15 +420,11 @@ impl Generator {
 
 
 pub fn is_vowel(c: char) -> bool {
-    if c == 'a' || c == 'e' || c == 'i' || c == 'o' || c == 'u' || c == 'A' || c == 'E' || c == 'I' || c == 'O' || c == 'U' {
-        true
-    } else {
-        false
-    }
+    c == 'a' || c == 'e' || c == 'i' || c == 'o' || c == 'u' || c == 'A' || c == 'E' || c == 'I' || c == 'O' || c == 'U'
 }
 
 pub fn capitalize(name: &str) -> String {
-    let mut name = name.to_string();
+    let name = name.to_string();
     name.chars().next().unwrap().to_uppercase().collect::<String>() + &name[1..]
 }
 
@@ -425,11 +451,9 @@ pub fn is_readable(name: &str) -> bool {
             return false;
         }
     }
-    if {
-        name.chars().nth(0) == name.chars().nth(name.len() - 1) ||
-        name.chars().nth(name.len() - 1) == Some('c') &&
-        name.chars().nth(name.len() - 2) == Some('c')
-    } {
+    if name.chars().next() == name.chars().nth(name.len() - 1) ||
+    name.chars().nth(name.len() - 1) == Some('c') &&
+    name.chars().nth(name.len() - 2) == Some('c') {
         return false;
     }
     true
@@ -0,0 +1,251 @@
+//! Checks that games play out the same way they do in the Java version of
+//! Alt League Blaseball.
+//!
+//! A fixture is a directory holding a matchup and the play-by-play the Java
+//! game printed for it:
+//!
+//! ```text
+//! fixture.properties    home, away (team names), day, and optionally seed
+//! league.properties     A league save with both teams, see `importer`
+//! teams/...
+//! players/...
+//! log.txt               Everything the Java game printed, one line per line
+//! ```
+//!
+//! If `seed` is left out, the game works out its seed the same way the
+//! Java version does, see `Game::java_seed()`. The logs are compared line by line, ignoring blank
+//! lines and trailing whitespace.
+//!
+//! The Java game only leaves text behind, so that's what gets compared. To
+//! make a mismatch easier to chase down, the replay keeps track of which
+//! step printed each line, and a `Divergence` carries the `Event`s of the
+//! step behind the first line that doesn't match.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::event::Event;
+use crate::game::Game;
+use crate::importer::{self, read, ImportError, Properties};
+use crate::java_random::Random;
+use crate::rules::Rules;
+use crate::team::Team;
+
+/// How many lines before a divergence get shown with it.
+pub const CONTEXT_LINES: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    pub name: String,
+    pub home: Team,
+    pub away: Team,
+    pub day: usize,
+    pub seed: Option<i64>,
+    pub expected: Vec<String>, // The Java game's log
+}
+
+impl Fixture {
+    pub fn load(dir: &Path) -> Result<Self, ImportError> {
+        let properties = Properties::from_text(&read(&dir.join("fixture.properties"))?)?;
+        // Ids don't change anything about how a game plays out.
+        let league = importer::load_league(dir, &mut Random::new(0))?;
+        let team = |field: &str| -> Result<Team, ImportError> {
+            let name = properties.get(field)?;
+            league.find_by_name(name).cloned().ok_or_else(|| ImportError::InvalidValue {
+                field: field.to_string(),
+                value: name.to_string(),
+            })
+        };
+        let seed = match properties.get("seed") {
+            Ok(_) => Some(properties.parse("seed")?),
+            Err(_) => None,
+        };
+
+        Ok(Fixture {
+            name: dir.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+            home: team("home")?,
+            away: team("away")?,
+            day: properties.parse("day")?,
+            seed,
+            expected: normalize(&read(&dir.join("log.txt"))?),
+        })
+    }
+
+    /// Plays the fixture's matchup and returns everything it logged, along
+    /// with the events of the step that logged each line.
+    pub fn replay(&self) -> Vec<(String, Vec<Event>)> {
+        let (home, away) = (self.home.clone(), self.away.clone());
+        let mut game = match self.seed {
+            Some(seed) => Game::with_seed(home, away, self.day, 0, Rules::default(), seed),
+            None => {
+                let seed = Game::java_seed(self.day, &home, &away);
+                Game::with_seed(home, away, self.day, 0, Rules::default(), seed)
+            },
+        };
+
+        let mut replay = Vec::new();
+        while !game.is_over() {
+            let events = game.step();
+            let mut log = String::new();
+            while let Some((message, _)) = game.message_log.pop_front() {
+                log.push_str(&message.message_line());
+                log.push('\n');
+            }
+            replay.extend(normalize(&log).into_iter().map(|line| (line, events.clone())));
+        }
+        replay
+    }
+
+    /// Replays the fixture and compares it against the Java log.
+    pub fn check(&self) -> Result<(), Box<Divergence>> {
+        let replay = self.replay();
+        let actual: Vec<String> = replay.iter().map(|(line, _)| line.clone()).collect();
+        match first_divergence(&self.expected, &actual) {
+            Some(mut divergence) => {
+                divergence.fixture = self.name.clone();
+                // If the replay ran out first, the last step is the one that
+                // should have kept going
+                divergence.events = replay.get(divergence.line)
+                    .or(replay.last())
+                    .map(|(_, events)| events.clone())
+                    .unwrap_or_default();
+                Err(Box::new(divergence))
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+/// Loads every fixture in `dir`, one per subdirectory, sorted by name.
+pub fn load_fixtures(dir: &Path) -> Result<Vec<Fixture>, ImportError> {
+    let entries = fs::read_dir(dir).map_err(|error| ImportError::Io(dir.to_path_buf(), error))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| Fixture::load(path)).collect()
+}
+
+/// The first line where two logs stop matching.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    pub fixture: String,
+    pub line: usize, // Starts at 0
+    pub expected: Option<String>, // None if the expected log ended first
+    pub actual: Option<String>, // None if the actual log ended first
+    pub context: Vec<String>, // The lines both logs agreed on right before
+    pub events: Vec<Event>, // What happened in the step that logged `actual`
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} diverges from the Java log at line {}:", self.fixture, self.line + 1)?;
+        let first = self.line - self.context.len();
+        for (i, line) in self.context.iter().enumerate() {
+            writeln!(f, "   {:>5}  {}", first + i + 1, line)?;
+        }
+        let end = "<end of log>".to_string();
+        writeln!(f, " - {:>5}  {}", self.line + 1, self.expected.as_ref().unwrap_or(&end))?;
+        write!(f, " + {:>5}  {}", self.line + 1, self.actual.as_ref().unwrap_or(&end))?;
+        if !self.events.is_empty() {
+            write!(f, "\nwhich came from:")?;
+        }
+        for event in self.events.iter() {
+            let half = if event.top { "top" } else { "bottom" };
+            write!(f, "\n    {} of {}, {}-{}: {:?}", half, event.inning, event.count_before.0, event.count_before.1, event.kind)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two logs, returning where they first differ along with the
+/// `CONTEXT_LINES` lines leading up to it.
+pub fn first_divergence(expected: &[String], actual: &[String]) -> Option<Divergence> {
+    let line = (0..expected.len().max(actual.len())).find(|i| expected.get(*i) != actual.get(*i))?;
+    Some(Divergence {
+        fixture: String::new(),
+        line,
+        expected: expected.get(line).cloned(),
+        actual: actual.get(line).cloned(),
+        context: expected[line.saturating_sub(CONTEXT_LINES)..line].to_vec(),
+        events: Vec::new(),
+    })
+}
+
+// Splits a log into lines, dropping blank lines and trailing whitespace.
+fn normalize(log: &str) -> Vec<String> {
+    log.lines()
+        .map(|line| line.trim_end().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[test]
+fn test_first_divergence() {
+    let expected = normalize("Blay pall!\nBall. 1-0\n\nBall. 2-0\nFoul ball. 2-1\nBall. 3-1\n");
+    let mut actual = expected.clone();
+    assert_eq!(first_divergence(&expected, &actual), None);
+
+    actual[3] = "Strike, swinging. 2-1".to_string();
+    let divergence = first_divergence(&expected, &actual).unwrap();
+    assert_eq!(divergence.line, 3);
+    assert_eq!(divergence.expected.as_deref(), Some("Foul ball. 2-1"));
+    assert_eq!(divergence.actual.as_deref(), Some("Strike, swinging. 2-1"));
+    assert_eq!(divergence.context, expected[..3].to_vec());
+
+    let divergence = first_divergence(&expected, &expected[..2]).unwrap();
+    assert_eq!(divergence.line, 2);
+    assert_eq!(divergence.actual, None);
+}
+
+#[test]
+fn test_divergence_points_at_the_events() {
+    let mut rng = Random::new(3);
+    let mut fixture = Fixture {
+        name: "swapped".to_string(),
+        home: Team::empty(&mut rng),
+        away: Team::empty(&mut rng),
+        day: 1,
+        seed: Some(5),
+        expected: Vec::new(),
+    };
+    let replay = fixture.replay();
+    fixture.expected = replay.iter().map(|(line, _)| line.clone()).collect();
+    assert_eq!(fixture.check(), Ok(()));
+
+    // Every line is tagged with the step that printed it
+    assert!(replay.iter().all(|(_, events)| !events.is_empty()));
+
+    let line = fixture.expected.len() / 2;
+    fixture.expected[line] = "Something else happened.".to_string();
+    let divergence = fixture.check().unwrap_err();
+    assert_eq!(divergence.line, line);
+    assert_eq!(divergence.events, replay[line].1);
+    assert!(divergence.to_string().contains("which came from:"));
+
+    // The Java game kept going after this one stopped
+    fixture.expected[line] = replay[line].0.clone();
+    fixture.expected.push("One more thing.".to_string());
+    let divergence = fixture.check().unwrap_err();
+    assert_eq!(divergence.actual, None);
+    assert_eq!(divergence.events, replay.last().unwrap().1);
+}
+
+// Ignored until there's a log from the Java game to check against. Run it
+// with `cargo test -- --ignored` once fixtures/parity has one.
+#[test]
+#[ignore = "no Java golden logs in fixtures/parity yet, see its README"]
+fn test_java_parity() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("parity");
+    let fixtures = load_fixtures(&dir).unwrap_or_else(|error| panic!("couldn't load the parity fixtures: {}", error));
+    assert!(!fixtures.is_empty(), "no parity fixtures in {}, so there's nothing to check against", dir.display());
+
+    let failures: Vec<String> = fixtures
+        .iter()
+        .filter_map(|fixture| fixture.check().err())
+        .map(|divergence| divergence.to_string())
+        .collect();
+    assert!(failures.is_empty(), "\n{}", failures.join("\n\n"));
+}
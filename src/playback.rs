@@ -0,0 +1,229 @@
+//! Plays a game's messages back at the times they were logged at, without
+//! blocking. Instead of sleeping until the next message is due, ask
+//! `Playback::poll()` for whatever's due now, and `Playback::wake_at()` for
+//! when to ask again.
+//!
+//! ```
+//! use alt_league_blaseball::clock::ManualClock;
+//! use alt_league_blaseball::game::Game;
+//! use alt_league_blaseball::java_random::Random;
+//! use alt_league_blaseball::playback::{Playback, Speed};
+//! use alt_league_blaseball::rules::Rules;
+//! use alt_league_blaseball::team::Team;
+//!
+//! let mut rng = Random::new(1);
+//! let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules::default());
+//! game.simulate_game();
+//!
+//! let clock = ManualClock::new(0);
+//! let mut playback = Playback::new(game.start_time);
+//! playback.extend(&mut game.message_log);
+//! playback.set_speed(Speed::Times(10), &clock);
+//! playback.play(&clock);
+//! while !playback.is_finished() {
+//!     for message in playback.poll(&clock) {
+//!         println!("{}", message.message_line());
+//!     }
+//!     if let Some(time) = playback.wake_at(&clock) {
+//!         clock.set(time);
+//!     }
+//! }
+//! ```
+
+use crate::clock::Clock;
+use crate::messaging::{Message, MessageLog};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speed {
+    /// Plays back this many times faster than the game was logged at. `Times(1)` is real time.
+    Times(u32),
+    /// Everything that's been logged is due right away.
+    Instant,
+}
+
+impl Default for Speed {
+    fn default() -> Self {
+        Speed::Times(1)
+    }
+}
+
+/// A queue of messages that come due as a `Clock` moves forward.
+///
+/// Positions are in game time, the same timeline the messages were logged
+/// on. A message is due once the position reaches its time. Playback starts
+/// out paused.
+#[derive(Debug, Clone)]
+pub struct Playback {
+    messages: Vec<(Message, u128)>,
+    next: usize, // Index of the first message that hasn't been played yet
+    position: u128, // Game time as of `anchor`
+    anchor: Option<u128>, // Clock time the position was taken at, None while paused
+    speed: Speed,
+}
+
+impl Playback {
+    /// Creates an empty, paused playback positioned at `start`, usually the game's `start_time`.
+    pub fn new(start: u128) -> Self {
+        Playback {
+            messages: Vec::new(),
+            next: 0,
+            position: start,
+            anchor: None,
+            speed: Speed::default(),
+        }
+    }
+
+    /// Moves every message out of `log` and onto the end of the queue.
+    pub fn extend(&mut self, log: &mut MessageLog) {
+        while let Some(message) = log.pop_front() {
+            self.messages.push(message);
+        }
+    }
+
+    pub fn play(&mut self, clock: &dyn Clock) {
+        if self.anchor.is_none() {
+            self.anchor = Some(clock.now());
+        }
+    }
+
+    pub fn pause(&mut self, clock: &dyn Clock) {
+        self.position = self.position(clock);
+        self.anchor = None;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.anchor.is_none()
+    }
+
+    pub fn speed(&self) -> Speed {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: Speed, clock: &dyn Clock) {
+        self.rebase(clock);
+        self.speed = speed;
+    }
+
+    /// Jumps to `time`. Messages logged before it are skipped, or come due again when seeking backwards.
+    pub fn seek(&mut self, time: u128, clock: &dyn Clock) {
+        self.position = time;
+        if self.anchor.is_some() {
+            self.anchor = Some(clock.now());
+        }
+        self.next = self.messages.partition_point(|(_, logged)| *logged < time);
+    }
+
+    /// Where playback is in game time.
+    pub fn position(&self, clock: &dyn Clock) -> u128 {
+        match (self.anchor, self.speed) {
+            (None, _) => self.position,
+            // Instant playback is always caught up with the last message.
+            (Some(_), Speed::Instant) => self.messages.last().map_or(self.position, |(_, logged)| self.position.max(*logged)),
+            (Some(anchor), Speed::Times(times)) => self.position + clock.now().saturating_sub(anchor) * times as u128,
+        }
+    }
+
+    /// Returns every message that's come due since the last poll.
+    pub fn poll(&mut self, clock: &dyn Clock) -> Vec<Message> {
+        let position = self.position(clock);
+        let due = self.messages[self.next..].partition_point(|(_, logged)| *logged <= position);
+        let messages = self.messages[self.next..self.next + due].iter().map(|(message, _)| message.clone()).collect();
+        self.next += due;
+        messages
+    }
+
+    /// The clock time the next message will come due at, or `None` if
+    /// there's nothing left to play or playback is paused.
+    pub fn wake_at(&self, clock: &dyn Clock) -> Option<u128> {
+        let anchor = self.anchor?;
+        let (_, logged) = self.messages.get(self.next)?;
+        let now = clock.now();
+        match self.speed {
+            Speed::Instant => Some(now),
+            Speed::Times(0) => None,
+            Speed::Times(times) => {
+                // Rounded up, so the message is always due by then.
+                let wait = logged.saturating_sub(self.position).div_ceil(times as u128);
+                Some((anchor + wait).max(now))
+            },
+        }
+    }
+
+    /// Whether every message that's been logged so far has been played.
+    pub fn is_finished(&self) -> bool {
+        self.next == self.messages.len()
+    }
+
+    // Moves the anchor up to now, so speed changes only affect what comes after.
+    fn rebase(&mut self, clock: &dyn Clock) {
+        self.position = self.position(clock);
+        if self.anchor.is_some() {
+            self.anchor = Some(clock.now());
+        }
+    }
+}
+
+#[cfg(test)]
+fn test_log(times: &[u128]) -> MessageLog {
+    let mut log = MessageLog::new();
+    for (i, time) in times.iter().enumerate() {
+        log.log(Message::Out(i as i32), *time, false);
+    }
+    log
+}
+
+#[test]
+fn test_playback_speeds() {
+    use crate::clock::ManualClock;
+
+    let clock = ManualClock::new(10_000);
+    let mut playback = Playback::new(0);
+    playback.extend(&mut test_log(&[0, 2_000, 4_000, 6_000]));
+
+    // Paused playback doesn't move past where it is
+    assert_eq!(playback.poll(&clock), vec![Message::Out(0)]);
+    assert_eq!(playback.wake_at(&clock), None);
+    clock.advance(5_000);
+    assert!(playback.poll(&clock).is_empty());
+
+    playback.play(&clock);
+    assert_eq!(playback.wake_at(&clock), Some(17_000));
+    clock.advance(1_999);
+    assert!(playback.poll(&clock).is_empty());
+    clock.advance(1);
+    assert_eq!(playback.poll(&clock), vec![Message::Out(1)]);
+
+    playback.set_speed(Speed::Times(10), &clock);
+    assert_eq!(playback.wake_at(&clock), Some(17_200));
+    clock.advance(200);
+    assert_eq!(playback.poll(&clock), vec![Message::Out(2)]);
+
+    playback.set_speed(Speed::Instant, &clock);
+    assert_eq!(playback.poll(&clock), vec![Message::Out(3)]);
+    assert!(playback.is_finished());
+    assert_eq!(playback.position(&clock), 6_000);
+}
+
+#[test]
+fn test_playback_pause_and_seek() {
+    use crate::clock::ManualClock;
+
+    let clock = ManualClock::new(0);
+    let mut playback = Playback::new(0);
+    playback.extend(&mut test_log(&[0, 2_000, 4_000, 6_000]));
+    playback.play(&clock);
+    clock.advance(1_000);
+    playback.pause(&clock);
+    assert_eq!(playback.poll(&clock), vec![Message::Out(0)]);
+
+    clock.advance(60_000);
+    assert_eq!(playback.position(&clock), 1_000);
+    assert!(playback.poll(&clock).is_empty());
+
+    playback.seek(4_000, &clock);
+    assert_eq!(playback.poll(&clock), vec![Message::Out(2)]);
+    playback.seek(0, &clock);
+    playback.play(&clock);
+    clock.advance(2_000);
+    assert_eq!(playback.poll(&clock), vec![Message::Out(0), Message::Out(1)]);
+}
@@ -1,9 +1,13 @@
-use crate::java_random::Random;
-use crate::name_generator::Generator;
+use crate::rng::GameRng;
+use crate::modifiers::{Modifier, ModifierDuration, ModifierKind};
+use crate::name_generator::{Generator, NamingSystem};
 use crate::player_stats::{CharacterStat, StatModifier, StatModifierType};
 use crate::player_flavor::{BloodType, CoffeeStyle, Fate, PregameRitual, Soulscream};
+use crate::seed::Subsystem;
+use crate::statistics::{Statistic, Statistics};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Player {
   pub name: String,
   
@@ -55,12 +59,23 @@ pub struct Player {
   fate: Fate,
   soulscream: Soulscream,
 
-  // statistics: Statistics, // TODO: Make a statistics struct
+  statistics: Statistics, // This season's stats
+  #[cfg_attr(feature = "serde", serde(default))]
+  modifiers: Vec<Modifier>,
 
   pub id: u32,
 }
 impl Player {
-  pub fn new(rng: &mut Random, name: String) -> Player {
+  /// The names of the stats returned by `stats()`, spelled the way ALB spells them.
+  pub const STAT_NAMES: [&'static str; 20] = [
+    "aggression", "arrogance", "carcinization", "damage", "density",
+    "dexterity", "dimensions", "effort", "focus", "fun",
+    "grit", "hitPoints", "malleability", "mathematics", "numberOfEyes",
+    "pinpointedness", "powder", "rejection", "splash", "wisdom",
+  ];
+
+  pub fn new(rng: &mut impl GameRng, name: String) -> Player {
+    let mut flavor = rng.split(Subsystem::Flavor);
     Player {
       name: name.clone(),
 
@@ -85,30 +100,32 @@ impl Player {
       splash: Player::random_unweighted_stat(rng),
       wisdom: Player::random_unweighted_stat(rng),
 
-      ritual: PregameRitual::get_random_ritual(rng),
-      coffee: CoffeeStyle::get_random_coffee_style(rng),
-      blood_type: BloodType::get_random_blood_type(rng),
-      fate: Fate::get_random_fate(rng),
+      ritual: PregameRitual::get_random_ritual(&mut flavor),
+      coffee: CoffeeStyle::get_random_coffee_style(&mut flavor),
+      blood_type: BloodType::get_random_blood_type(&mut flavor),
+      fate: Fate::get_random_fate(&mut flavor),
       soulscream: Soulscream::generate_soulscream(name),
       
-      // statistics: Statistics::new(),
+      statistics: Statistics::new(),
+      modifiers: Vec::new(),
 
       id: rng.next_u32(),
     }
   }
 
-  pub fn default(rng: &mut Random) -> Player {
-    let mut gen = Generator::new(rng.get_seed());
+  /// Creates a player with a random name. The name comes from an RNG of
+  /// the same kind as `rng`, split off from it.
+  pub fn default(rng: &mut impl GameRng) -> Player {
+    let mut gen = Generator::with_rng(rng.split(Subsystem::Names), NamingSystem::default());
     let name = gen.next_name_with_distribution() + " " + &gen.next_name_with_distribution();
-    rng.set_seed(gen.get_seed());
     Player::new(rng, name)
   }
 
-  pub fn random_stat(rng: &mut Random) -> CharacterStat {
+  pub fn random_stat(rng: &mut impl GameRng) -> CharacterStat {
     CharacterStat::new(rng.next_f64() * 2.5 + rng.next_f64() * 2.5)
   }
 
-  pub fn random_unweighted_stat(rng: &mut Random) -> CharacterStat {
+  pub fn random_unweighted_stat(rng: &mut impl GameRng) -> CharacterStat {
     CharacterStat::new(rng.next_f64() * 5.0)
   }
 
@@ -116,6 +133,65 @@ impl Player {
     self.name.clone()
   }
 
+  pub fn get_ritual(&self) -> &PregameRitual {
+    &self.ritual
+  }
+
+  pub fn get_coffee(&self) -> CoffeeStyle {
+    self.coffee
+  }
+
+  pub fn get_blood_type(&self) -> BloodType {
+    self.blood_type
+  }
+
+  pub fn get_fate(&self) -> &Fate {
+    &self.fate
+  }
+
+  pub fn get_soulscream(&self) -> &Soulscream {
+    &self.soulscream
+  }
+
+  pub fn set_ritual(&mut self, ritual: PregameRitual) {
+    self.ritual = ritual;
+  }
+
+  pub fn set_coffee(&mut self, coffee: CoffeeStyle) {
+    self.coffee = coffee;
+  }
+
+  pub fn set_blood_type(&mut self, blood_type: BloodType) {
+    self.blood_type = blood_type;
+  }
+
+  pub fn set_fate(&mut self, fate: Fate) {
+    self.fate = fate;
+  }
+
+  pub fn set_soulscream(&mut self, soulscream: Soulscream) {
+    self.soulscream = soulscream;
+  }
+
+  /// Returns all 20 stats in alphabetical order, which is the same order as `STAT_NAMES`.
+  pub fn stats(&self) -> [&CharacterStat; 20] {
+    [
+      &self.aggression, &self.arrogance, &self.carcinization, &self.damage, &self.density,
+      &self.dexterity, &self.dimensions, &self.effort, &self.focus, &self.fun,
+      &self.grit, &self.hit_points, &self.malleability, &self.mathematics, &self.number_of_eyes,
+      &self.pinpointedness, &self.powder, &self.rejection, &self.splash, &self.wisdom,
+    ]
+  }
+
+  pub fn stats_mut(&mut self) -> [&mut CharacterStat; 20] {
+    [
+      &mut self.aggression, &mut self.arrogance, &mut self.carcinization, &mut self.damage, &mut self.density,
+      &mut self.dexterity, &mut self.dimensions, &mut self.effort, &mut self.focus, &mut self.fun,
+      &mut self.grit, &mut self.hit_points, &mut self.malleability, &mut self.mathematics, &mut self.number_of_eyes,
+      &mut self.pinpointedness, &mut self.powder, &mut self.rejection, &mut self.splash, &mut self.wisdom,
+    ]
+  }
+
   pub fn get_batting(&self) -> f64 {
     (self.density.value() + self.number_of_eyes.value() / 2.0 + self.focus.value() / 4.0 + self.malleability.value() / 8.0 + self.splash.value() * 3.0 / 4.0 + self.aggression.value() * 3.0 / 32.0) / 2.28125
   }
@@ -197,16 +273,51 @@ impl Player {
   // pub fn walk_message(&self) -> String {}
 
   // Stuff for statistics
-  // pub fn clear_statistics(&mut self) {}
-  // pub fn add_statistic(&mut self, statistic: Statistic) {}
-  // pub fn add_statistic_with_value(&mut self, statistic: Statistic, value: f64) {}
+  pub fn clear_statistics(&mut self) {
+    self.statistics.clear();
+  }
+
+  pub fn add_statistic(&mut self, statistic: Statistic) {
+    self.statistics.add(statistic);
+  }
+
+  pub fn add_statistic_with_value(&mut self, statistic: Statistic, value: f64) {
+    self.statistics.add_with_value(statistic, value);
+  }
+
+  /// Adds a whole game's worth of stats to the player's season.
+  pub fn add_statistics(&mut self, statistics: &Statistics) {
+    self.statistics.merge(statistics);
+  }
+
   // pub fn print_statistics(&self) {}
-  // pub fn get_statistics(&self) -> Vec<Statistic> {}
+
+  pub fn get_statistics(&self) -> &Statistics {
+    &self.statistics
+  }
 
   // Stuff for modifiers
-  // pub fn add_modifier(&mut self, modifier: Modifier) {}
-  // pub fn has_modifier(&self, modifier: Modifier) -> bool {}
-  // pub fn remove_modifier(&mut self, modifier: Modifier) {}
+  pub fn add_modifier(&mut self, modifier: Modifier) {
+    self.modifiers.push(modifier);
+  }
+
+  pub fn has_modifier(&self, kind: ModifierKind) -> bool {
+    self.modifiers.iter().any(|modifier| modifier.kind == kind)
+  }
+
+  /// Removes every modifier of the given kind, however long it was going to last.
+  pub fn remove_modifier(&mut self, kind: ModifierKind) {
+    self.modifiers.retain(|modifier| modifier.kind != kind);
+  }
+
+  pub fn get_modifiers(&self) -> &[Modifier] {
+    &self.modifiers
+  }
+
+  /// Removes the modifiers that only last as long as `duration`, or less.
+  pub fn expire_modifiers(&mut self, duration: ModifierDuration) {
+    self.modifiers.retain(|modifier| modifier.outlasts(duration));
+  }
 }
 
 impl PartialEq for Player {
@@ -1,7 +1,9 @@
 use crate::java_random::Random;
+use crate::rng::GameRng;
 
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CoffeeStyle {
   Latte,
   ColdBrew,
@@ -63,13 +65,19 @@ impl CoffeeStyle {
     }
   }
 
-  pub fn get_random_coffee_style(rng: &mut Random) -> CoffeeStyle {
+  /// Finds the coffee style with the given name, as returned by `as_str()`.
+  pub fn from_name(name: &str) -> Option<CoffeeStyle> {
+    COFFEE_VARIANTS.iter().copied().find(|coffee| coffee.as_str() == name)
+  }
+
+  pub fn get_random_coffee_style(rng: &mut impl GameRng) -> CoffeeStyle {
     let x = rng.next_f64() * COFFEE_VARIANTS.len() as f64;
     COFFEE_VARIANTS[x as usize]
   }
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BloodType {
   A,
   Aa,
@@ -137,15 +145,21 @@ impl BloodType {
     }
   }
 
-  pub fn get_random_blood_type(rng: &mut Random) -> BloodType {
+  /// Finds the blood type with the given name, as returned by `as_str()`.
+  pub fn from_name(name: &str) -> Option<BloodType> {
+    BLOOD_TYPES.iter().copied().find(|blood_type| blood_type.as_str() == name)
+  }
+
+  pub fn get_random_blood_type(rng: &mut impl GameRng) -> BloodType {
     let x = rng.next_f64() * BLOOD_TYPES.len() as f64;
     BLOOD_TYPES[x as usize]
   }
 }
 
-const SOULSCREAM_CHARS: &'static str = "AEIOUHX";
+const SOULSCREAM_CHARS: &str = "AEIOUHX";
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Soulscream(pub String);
 impl Soulscream {
   pub fn generate_soulscream(name: String) -> Soulscream {
@@ -174,16 +188,16 @@ impl Soulscream {
     
     Soulscream(soulscream)
   }
-  fn new_from_str(soulscream: String) -> Soulscream {
+  pub fn new_from_str(soulscream: String) -> Soulscream {
     Soulscream(soulscream)
   }
-  fn as_str(&self) -> &str {
+  pub fn as_str(&self) -> &str {
     let Soulscream(scream) = self;
     scream.as_str()
   }
 }
 
-const PREGAME_RITUALS: [&'static str; 343] = [
+const PREGAME_RITUALS: [&str; 343] = [
   "Trying their best",
   "Eating",
   "Sleeping",
@@ -528,15 +542,23 @@ const PREGAME_RITUALS: [&'static str; 343] = [
   "Picking flowers",
   "Side jobs"
 ];
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PregameRitual(String);
 impl PregameRitual {
-  pub fn get_random_ritual(rng: &mut Random) -> PregameRitual {
+  pub fn get_random_ritual(rng: &mut impl GameRng) -> PregameRitual {
     let x = rng.next_f64() * PREGAME_RITUALS.len() as f64;
     let ritual = PREGAME_RITUALS[x as usize];
 
     PregameRitual(ritual.to_string())
   }
+  pub fn new_from_str(ritual: String) -> PregameRitual {
+    PregameRitual(ritual)
+  }
+  pub fn as_str(&self) -> &str {
+    let PregameRitual(ritual) = self;
+    ritual.as_str()
+  }
   pub fn is_valid(&self) -> bool {
     let PregameRitual(ritual_string) = self;
     let ritual_string = ritual_string.clone();
@@ -545,15 +567,19 @@ impl PregameRitual {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Fate(u8);
 impl Fate {
-  pub fn get_random_fate(rng: &mut Random) -> Fate {
+  pub fn get_random_fate(rng: &mut impl GameRng) -> Fate {
     let fate = rng.next_f64() * 100.0;
     Fate(fate as u8)
   }
+  pub fn new(fate: u8) -> Fate {
+    Fate(fate)
+  }
   pub fn as_u8(&self) -> u8 {
     let Fate(value) = self;
-    value.clone()
+    *value
   }
   pub fn is_valid(&self) -> bool {
     self.as_u8() <= 100
@@ -2,6 +2,7 @@ use std::{ops::Add};
 
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterStat {
   pub base_value: f64,
   stat_modifiers: Vec<StatModifier>,
@@ -25,7 +26,7 @@ impl CharacterStat {
 
   pub fn add_modifier(&mut self, modifier: StatModifier) {
     self.stat_modifiers.push(modifier);
-    self.stat_modifiers.sort_by(|a, b| a.order.cmp(&b.order));
+    self.stat_modifiers.sort_by_key(|a| a.order);
   }
 
   pub fn remove_modifier(&mut self, modifier: StatModifier) {
@@ -68,7 +69,7 @@ impl Add for CharacterStat {
   type Output = Self;
   fn add(self, other: Self) -> Self {
     let base_value = self.base_value + other.base_value;
-    let stat_modifiers = self.get_stat_modifiers().into_iter().chain(other.get_stat_modifiers().into_iter()).collect();
+    let stat_modifiers = self.get_stat_modifiers().into_iter().chain(other.get_stat_modifiers()).collect();
 
     CharacterStat {
       base_value,
@@ -78,18 +79,21 @@ impl Add for CharacterStat {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StatModifierType {
   Flat,
   Percent,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StatModifierLongetivity {
   Permanent,
   Temporary,
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatModifier {
   value: f64,
   stat_modifier_type: StatModifierType,
@@ -108,7 +112,7 @@ impl StatModifier {
   }
 
   pub fn new_without_order(value: f64, stat_modifier_type: StatModifierType, stat_modifier_longetivity: StatModifierLongetivity) -> Self {
-    let order = stat_modifier_type.clone() as i32;
+    let order = stat_modifier_type as i32;
     StatModifier::new(value, stat_modifier_type, stat_modifier_longetivity, order)
   }
 
@@ -125,11 +129,11 @@ impl StatModifier {
   }
 
   pub fn get_type(&mut self) -> StatModifierType {
-    self.stat_modifier_type.clone()
+    self.stat_modifier_type
   }
 
   pub fn get_longetivity(&mut self) -> StatModifierLongetivity {
-    self.stat_modifier_longetivity.clone()
+    self.stat_modifier_longetivity
   }
 
   pub fn get_order(&mut self) -> i32 {
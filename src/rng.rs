@@ -0,0 +1,442 @@
+//! The RNGs the engine can roll with.
+//!
+//! Everything that rolls takes a `GameRng`, which is only the handful of
+//! draws the engine actually makes. `Random` rolls exactly like the Java
+//! version, for parity. `CounterRng` is a counter-based generator with
+//! better statistics for when parity doesn't matter. `RecordingRng` wraps
+//! either one and logs every draw, so a game can be replayed from its
+//! draws, or have its rolls scripted by hand in tests.
+//!
+//! Anything that has to keep its RNG around, like `Game` and `Weather`,
+//! stores an `RngBackend`, which can be any of the three.
+//!
+//! ```
+//! use alt_league_blaseball::rng::{Draw, GameRng, RecordingRng, RngBackend, RngKind};
+//!
+//! let mut rng = RecordingRng::record(RngKind::Counter.seeded(3));
+//! let roll = rng.next_f64();
+//! assert_eq!(rng.draws(), &[Draw::F64(roll)]);
+//!
+//! let mut scripted = RngBackend::Recording(Box::new(RecordingRng::replay(vec![Draw::F64(0.99)])));
+//! assert_eq!(scripted.next_f64(), 0.99);
+//! ```
+
+use crate::java_random::Random;
+use crate::seed::{mix, Seed, Subsystem, GOLDEN_GAMMA};
+
+/// The draws the engine makes.
+pub trait GameRng {
+    /// Returns a f64 uniformly distributed between 0.0 and 1.0.
+    fn next_f64(&mut self) -> f64;
+
+    /// Returns a uniformly distributed unsigned 32-bit integer.
+    fn next_u32(&mut self) -> u32;
+
+    /// Returns a random number in the range [0, max).
+    fn next_u32_bound(&mut self, max: u32) -> u32;
+
+    /// Returns a uniformly distributed signed 64-bit integer.
+    fn next_i64(&mut self) -> i64;
+
+    /// Returns a boolean value that has an equal chance of being true or false.
+    fn next_bool(&mut self) -> bool;
+
+    /// Which kind of RNG this is, or wraps.
+    fn kind(&self) -> RngKind;
+
+    /// Splits off an RNG of the same kind for one subsystem, seeded from a
+    /// single draw of this one.
+    fn split(&mut self, subsystem: Subsystem) -> RngBackend {
+        let seed = Seed::new(self.next_i64()).subsystem(subsystem);
+        self.kind().seeded(seed.value())
+    }
+}
+
+impl GameRng for Random {
+    fn next_f64(&mut self) -> f64 {
+        Random::next_f64(self)
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        Random::next_u32(self)
+    }
+
+    fn next_u32_bound(&mut self, max: u32) -> u32 {
+        Random::next_u32_bound(self, max)
+    }
+
+    fn next_i64(&mut self) -> i64 {
+        Random::next_i64(self)
+    }
+
+    fn next_bool(&mut self) -> bool {
+        Random::next_bool(self)
+    }
+
+    fn kind(&self) -> RngKind {
+        RngKind::Java
+    }
+}
+
+/// Which kind of RNG to seed, for choosing one by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RngKind {
+    /// `Random`, rolling the same as the Java version.
+    #[default]
+    Java,
+    /// `CounterRng`.
+    Counter,
+}
+
+const RNG_KINDS: [RngKind; 2] = [RngKind::Java, RngKind::Counter];
+
+impl RngKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RngKind::Java => "java",
+            RngKind::Counter => "counter",
+        }
+    }
+
+    /// Finds the kind with the given name, as returned by `as_str()`.
+    pub fn from_name(name: &str) -> Option<RngKind> {
+        RNG_KINDS.iter().copied().find(|kind| kind.as_str() == name)
+    }
+
+    pub fn seeded(&self, seed: i64) -> RngBackend {
+        match self {
+            RngKind::Java => RngBackend::Java(Random::new(seed)),
+            RngKind::Counter => RngBackend::Counter(CounterRng::new(seed)),
+        }
+    }
+}
+
+/// Any of the RNGs, for storing one without knowing which.
+///
+/// Serialized as the RNG itself, so a `Random` saved before there were other
+/// kinds loads as `Java`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum RngBackend {
+    Java(Random),
+    Counter(CounterRng),
+    Recording(Box<RecordingRng>),
+}
+
+impl Default for RngBackend {
+    fn default() -> Self {
+        RngBackend::Java(Random::new(0))
+    }
+}
+
+impl RngBackend {
+    fn rng(&mut self) -> &mut dyn GameRng {
+        match self {
+            RngBackend::Java(rng) => rng,
+            RngBackend::Counter(rng) => rng,
+            RngBackend::Recording(rng) => rng.as_mut(),
+        }
+    }
+}
+
+impl GameRng for RngBackend {
+    fn next_f64(&mut self) -> f64 {
+        self.rng().next_f64()
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        self.rng().next_u32()
+    }
+
+    fn next_u32_bound(&mut self, max: u32) -> u32 {
+        self.rng().next_u32_bound(max)
+    }
+
+    fn next_i64(&mut self) -> i64 {
+        self.rng().next_i64()
+    }
+
+    fn next_bool(&mut self) -> bool {
+        self.rng().next_bool()
+    }
+
+    fn kind(&self) -> RngKind {
+        match self {
+            RngBackend::Java(_) => RngKind::Java,
+            RngBackend::Counter(_) => RngKind::Counter,
+            RngBackend::Recording(rng) => rng.kind(),
+        }
+    }
+}
+
+/// A counter-based RNG: the nth number is a hash of the seed and n
+/// (SplitMix64), so jumping anywhere in the stream is free. Passes the
+/// statistical tests the Java LCG fails, but rolls nothing like it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CounterRng {
+    key: u64,
+    counter: u64, // How many numbers have been drawn
+}
+
+impl CounterRng {
+    pub fn new(seed: i64) -> Self {
+        CounterRng {
+            key: mix(seed as u64),
+            counter: 0,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.counter = self.counter.wrapping_add(1);
+        mix(self.key.wrapping_add(self.counter.wrapping_mul(GOLDEN_GAMMA)))
+    }
+
+    /// Skips the next `steps` numbers.
+    pub fn advance(&mut self, steps: u64) {
+        self.counter = self.counter.wrapping_add(steps);
+    }
+
+    /// How many numbers have been drawn (or skipped) so far.
+    pub fn position(&self) -> u64 {
+        self.counter
+    }
+}
+
+impl GameRng for CounterRng {
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// # Panics
+    /// If `max` is 0, this function panics.
+    fn next_u32_bound(&mut self, max: u32) -> u32 {
+        if max == 0 {
+            panic!("Maximum must > 0")
+        }
+
+        // Lemire's method, rejecting the few values that would make it uneven
+        let threshold = max.wrapping_neg() % max;
+        loop {
+            let product = self.next_u32() as u64 * max as u64;
+            if product as u32 >= threshold {
+                return (product >> 32) as u32;
+            }
+        }
+    }
+
+    fn next_i64(&mut self) -> i64 {
+        self.next_u64() as i64
+    }
+
+    fn next_bool(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+
+    fn kind(&self) -> RngKind {
+        RngKind::Counter
+    }
+}
+
+/// One draw from a `GameRng`, and what it came out as.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Draw {
+    F64(f64),
+    U32(u32),
+    Bounded(u32, u32), // Max, value
+    I64(i64),
+    Bool(bool),
+}
+
+/// Logs every draw made from it. It replays any draws it was given first,
+/// then draws from the RNG it wraps, if it wraps one.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordingRng {
+    inner: Option<RngBackend>,
+    draws: Vec<Draw>,
+    position: usize, // The next draw to replay
+}
+
+impl RecordingRng {
+    /// Records every draw from `inner`.
+    pub fn record(inner: RngBackend) -> Self {
+        RecordingRng {
+            inner: Some(inner),
+            draws: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Replays `draws`, in order.
+    ///
+    /// Draws have to be asked for in the same order, the same way they were
+    /// made (the same kind, and the same max for bounded draws), and there
+    /// have to be enough of them, or drawing panics.
+    pub fn replay(draws: Vec<Draw>) -> Self {
+        RecordingRng {
+            inner: None,
+            draws,
+            position: 0,
+        }
+    }
+
+    /// Works the same as `replay()`, but carries on with `inner` once the draws run out.
+    pub fn replay_then(draws: Vec<Draw>, inner: RngBackend) -> Self {
+        RecordingRng {
+            inner: Some(inner),
+            draws,
+            position: 0,
+        }
+    }
+
+    /// Every draw so far, along with any that haven't been replayed yet.
+    pub fn draws(&self) -> &[Draw] {
+        &self.draws
+    }
+
+    pub fn into_draws(self) -> Vec<Draw> {
+        self.draws
+    }
+
+    /// How many draws are left to replay.
+    pub fn remaining(&self) -> usize {
+        self.draws.len() - self.position
+    }
+
+    // Replays the next draw if there is one, making sure it's the same kind
+    // as `matches` expects. Otherwise draws a new one from the wrapped RNG.
+    fn draw(&mut self, asked: &str, matches: impl Fn(&Draw) -> bool, make: impl FnOnce(&mut RngBackend) -> Draw) -> Draw {
+        let draw = match self.draws.get(self.position) {
+            Some(draw) if matches(draw) => *draw,
+            Some(draw) => panic!("draw {} was {:?}, but it's being replayed as {}", self.position, draw, asked),
+            None => {
+                let Some(inner) = self.inner.as_mut() else {
+                    panic!("ran out of draws to replay at {}", asked);
+                };
+                let draw = make(inner);
+                self.draws.push(draw);
+                draw
+            },
+        };
+        self.position += 1;
+        draw
+    }
+}
+
+impl GameRng for RecordingRng {
+    fn next_f64(&mut self) -> f64 {
+        match self.draw("F64", |draw| matches!(draw, Draw::F64(_)), |rng| Draw::F64(rng.next_f64())) {
+            Draw::F64(value) => value,
+            _ => unreachable!(),
+        }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        match self.draw("U32", |draw| matches!(draw, Draw::U32(_)), |rng| Draw::U32(rng.next_u32())) {
+            Draw::U32(value) => value,
+            _ => unreachable!(),
+        }
+    }
+
+    fn next_u32_bound(&mut self, max: u32) -> u32 {
+        let asked = format!("Bounded({}, _)", max);
+        match self.draw(&asked, |draw| matches!(draw, Draw::Bounded(bound, _) if *bound == max), |rng| Draw::Bounded(max, rng.next_u32_bound(max))) {
+            Draw::Bounded(_, value) => value,
+            _ => unreachable!(),
+        }
+    }
+
+    fn next_i64(&mut self) -> i64 {
+        match self.draw("I64", |draw| matches!(draw, Draw::I64(_)), |rng| Draw::I64(rng.next_i64())) {
+            Draw::I64(value) => value,
+            _ => unreachable!(),
+        }
+    }
+
+    fn next_bool(&mut self) -> bool {
+        match self.draw("Bool", |draw| matches!(draw, Draw::Bool(_)), |rng| Draw::Bool(rng.next_bool())) {
+            Draw::Bool(value) => value,
+            _ => unreachable!(),
+        }
+    }
+
+    /// The kind of the RNG it wraps. Replaying without one counts as `Java`.
+    fn kind(&self) -> RngKind {
+        self.inner.as_ref().map_or(RngKind::default(), GameRng::kind)
+    }
+}
+
+#[test]
+fn test_rng_backends() {
+    use crate::game::Game;
+    use crate::rules::Rules;
+    use crate::team::Team;
+    use crate::weather::Weather;
+
+    // The counter RNG can jump, and is spread out evenly
+    let mut counter = CounterRng::new(1);
+    let mut jumped = counter;
+    jumped.advance(10);
+    let rolls: Vec<u32> = (0..10_000).map(|_| counter.next_u32_bound(10)).collect();
+    assert_eq!(jumped.next_u64(), CounterRng { key: counter.key, counter: 10 }.next_u64());
+    for digit in 0..10 {
+        let count = rolls.iter().filter(|roll| **roll == digit).count();
+        assert!((900..1100).contains(&count), "{} came up {} times", digit, count);
+    }
+
+    // Record a whole game, then play it again from its draws alone
+    let play = |rng: RngBackend| {
+        let mut teams = Random::new(5);
+        let (home, away) = (Team::empty(&mut teams), Team::empty(&mut teams));
+        let mut game = Game::with_rng(home, away, 1, 0, Rules::default(), rng, Weather::Clear(RngBackend::default()));
+        game.simulate_game();
+        game
+    };
+    let recorded = play(RngBackend::Recording(Box::new(RecordingRng::record(RngKind::Counter.seeded(9)))));
+    let draws = match recorded.rng {
+        RngBackend::Recording(rng) => rng.into_draws(),
+        _ => unreachable!(),
+    };
+    assert_eq!(play(RngKind::Counter.seeded(9)).events, recorded.events);
+    assert_eq!(play(RngBackend::Recording(Box::new(RecordingRng::replay(draws)))).events, recorded.events);
+
+    // Scripted draws come out as written, then the wrapped RNG takes over
+    let mut scripted = RecordingRng::replay_then(vec![Draw::Bounded(6, 4), Draw::Bool(true)], RngKind::Java.seeded(2));
+    assert_eq!(scripted.next_u32_bound(6), 4);
+    assert!(scripted.next_bool());
+    assert_eq!(scripted.remaining(), 0);
+    assert_eq!(scripted.next_f64(), Random::new(2).next_f64());
+    assert_eq!(scripted.draws().len(), 3);
+}
+
+#[test]
+fn test_split_keeps_the_kind() {
+    use crate::name_generator::{Generator, NamingSystem};
+    use crate::player::Player;
+
+    for kind in RNG_KINDS {
+        assert_eq!(kind.seeded(4).split(Subsystem::Names).kind(), kind);
+        assert_eq!(RecordingRng::record(kind.seeded(4)).split(Subsystem::Flavor).kind(), kind);
+    }
+
+    // Players get their names from the same kind of RNG they're rolled with,
+    // seeded from the player's RNG mixed for `Subsystem::Names`.
+    for kind in RNG_KINDS {
+        let mut rng = kind.seeded(4);
+        let player = Player::default(&mut rng.clone());
+        let seed = Seed::new(rng.next_i64()).subsystem(Subsystem::Names).value();
+        let mut names = Generator::with_rng(kind.seeded(seed), NamingSystem::default());
+        assert_eq!(player.name, names.next_name_with_distribution() + " " + &names.next_name_with_distribution());
+    }
+    let mut rng = Random::new(4);
+    let mut names = Generator::new(Seed::new(rng.next_i64()).subsystem(Subsystem::Names).value());
+    assert_eq!(Player::default(&mut Random::new(4)).name, names.next_name_with_distribution() + " " + &names.next_name_with_distribution());
+}
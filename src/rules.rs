@@ -0,0 +1,80 @@
+//! The rules a game is played by. `Rules::default()` is regular Blaseball;
+//! change the fields for variants like a fourth base or shorter games.
+//!
+//! ```
+//! use alt_league_blaseball::rules::Rules;
+//!
+//! let fourth_strike = Rules { strikes: 4, ..Rules::default() };
+//! assert!(fourth_strike.validate().is_ok());
+//! assert!(Rules { outs: 0, ..Rules::default() }.validate().is_err());
+//! ```
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rules {
+    pub strikes: i32, // Strikes for a strikeout
+    pub balls: i32, // Balls for a walk
+    pub outs: i32, // Outs in a half inning
+    pub innings: u32, // Innings before the game can end
+    pub bases: usize, // Bases a runner touches before home, starting with first
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            strikes: 3,
+            balls: 4,
+            outs: 3,
+            innings: 9,
+            bases: 3,
+        }
+    }
+}
+
+impl Rules {
+    /// Checks every rule is at least 1. A game can't be played with no
+    /// strikes, balls, outs, innings or bases, so `Game` refuses rules that
+    /// don't pass.
+    pub fn validate(&self) -> Result<(), RulesError> {
+        let rules = [
+            ("strikes", self.strikes as i64),
+            ("balls", self.balls as i64),
+            ("outs", self.outs as i64),
+            ("innings", self.innings as i64),
+            ("bases", self.bases as i64),
+        ];
+        match rules.into_iter().find(|(_, value)| *value < 1) {
+            Some((rule, _)) => Err(RulesError::TooLow(rule)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Why `Rules::validate()` turned down a set of rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RulesError {
+    TooLow(&'static str), // Name of the rule that's under 1
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::TooLow(rule) => write!(f, "{} has to be at least 1", rule),
+        }
+    }
+}
+
+impl std::error::Error for RulesError {}
+
+#[test]
+fn test_validate() {
+    assert_eq!(Rules::default().validate(), Ok(()));
+    assert_eq!(Rules { strikes: 4, bases: 4, ..Rules::default() }.validate(), Ok(()));
+    assert_eq!(Rules { strikes: 0, ..Rules::default() }.validate(), Err(RulesError::TooLow("strikes")));
+    assert_eq!(Rules { balls: -1, ..Rules::default() }.validate(), Err(RulesError::TooLow("balls")));
+    assert_eq!(Rules { outs: 0, ..Rules::default() }.validate(), Err(RulesError::TooLow("outs")));
+    assert_eq!(Rules { innings: 0, ..Rules::default() }.validate(), Err(RulesError::TooLow("innings")));
+    assert_eq!(Rules { bases: 0, ..Rules::default() }.validate(), Err(RulesError::TooLow("bases")));
+}
@@ -0,0 +1,172 @@
+// Turns a player's numbers into something a fan can read:
+// star ratings, their best and worst stats, and a player card.
+
+use std::fmt;
+
+use crate::name_generator::capitalize;
+use crate::player::Player;
+
+/// A rating in stars, rounded to the nearest half star. One star is one
+/// point of rating, so an average player is around two and a half stars.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Stars(f64);
+
+impl Stars {
+  pub fn from_rating(rating: f64) -> Self {
+    Stars((rating.max(0.0) * 2.0).round() / 2.0)
+  }
+
+  pub fn value(&self) -> f64 {
+    self.0
+  }
+}
+
+impl fmt::Display for Stars {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let whole = self.0.floor() as usize;
+    let mut stars = "★".repeat(whole);
+    if self.0 > whole as f64 {
+      stars.push('½');
+    }
+    f.pad(&stars)
+  }
+}
+
+/// One of the 20 stats, by its readable name.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Attribute {
+  pub name: String, // Like "Number of Eyes"
+  pub value: f64,
+}
+
+/// Everything a scout would want to know about a player at a glance.
+/// Print it for the text version, or serialize it for the structured one.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ScoutingReport {
+  pub name: String,
+  pub soulscream: String,
+  pub ritual: String,
+  pub coffee: String,
+  pub blood_type: String,
+  pub fate: u8,
+
+  pub batting: Stars,
+  pub pitching: Stars,
+  pub baserunning: Stars,
+  pub defense: Stars,
+
+  pub best: Attribute,
+  pub worst: Attribute,
+}
+
+impl ScoutingReport {
+  pub fn new(player: &Player) -> Self {
+    ScoutingReport {
+      name: player.get_name(),
+      soulscream: player.get_soulscream().as_str().to_string(),
+      ritual: player.get_ritual().as_str().to_string(),
+      coffee: player.get_coffee().as_str().to_string(),
+      blood_type: player.get_blood_type().as_str().to_string(),
+      fate: player.get_fate().as_u8(),
+
+      batting: Stars::from_rating(player.get_batting()),
+      pitching: Stars::from_rating(player.get_pitching()),
+      baserunning: Stars::from_rating(player.get_baserunning()),
+      defense: Stars::from_rating(player.get_defense()),
+
+      best: best_attribute(player),
+      worst: worst_attribute(player),
+    }
+  }
+}
+
+impl fmt::Display for ScoutingReport {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "{}", self.name)?;
+    writeln!(f, "Soulscream: {}", self.soulscream)?;
+    writeln!(f, "Pregame Ritual: {}", self.ritual)?;
+    writeln!(f, "Coffee Style: {}", self.coffee)?;
+    writeln!(f, "Blood Type: {}", self.blood_type)?;
+    writeln!(f, "Fate: {}", self.fate)?;
+    for (label, stars) in [("Batting", self.batting), ("Pitching", self.pitching), ("Baserunning", self.baserunning), ("Defense", self.defense)] {
+      writeln!(f, "{:<12} {:<6} ({})", label, stars, stars.value())?;
+    }
+    writeln!(f, "Best: {} ({:.2})", self.best.name, self.best.value)?;
+    write!(f, "Worst: {} ({:.2})", self.worst.name, self.worst.value)
+  }
+}
+
+/// The player's highest stat, including any boosts they've got.
+/// Ties go to the one that comes first alphabetically.
+pub fn best_attribute(player: &Player) -> Attribute {
+  attributes(player)
+    .reduce(|best, attribute| if attribute.value > best.value { attribute } else { best })
+    .unwrap()
+}
+
+/// The player's lowest stat, including any boosts they've got.
+/// Ties go to the one that comes first alphabetically.
+pub fn worst_attribute(player: &Player) -> Attribute {
+  attributes(player)
+    .reduce(|worst, attribute| if attribute.value < worst.value { attribute } else { worst })
+    .unwrap()
+}
+
+fn attributes(player: &Player) -> impl Iterator<Item = Attribute> + '_ {
+  Player::STAT_NAMES.into_iter()
+    .zip(player.stats())
+    .map(|(name, stat)| Attribute {
+      name: readable_stat_name(name),
+      value: stat.value(),
+    })
+}
+
+// "numberOfEyes" => "Number of Eyes"
+fn readable_stat_name(name: &str) -> String {
+  let mut words: Vec<String> = Vec::new();
+  for c in name.chars() {
+    if c.is_uppercase() || words.is_empty() {
+      words.push(String::new());
+    }
+    words.last_mut().unwrap().push(c.to_ascii_lowercase());
+  }
+  words.iter()
+    .enumerate()
+    .map(|(i, word)| match word.as_str() {
+      "of" if i > 0 => word.clone(),
+      _ => capitalize(word),
+    })
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+#[test]
+fn test_scouting_report() {
+  use crate::java_random::Random;
+  use crate::player_stats::CharacterStat;
+
+  assert_eq!(Stars::from_rating(2.74).value(), 2.5);
+  assert_eq!(Stars::from_rating(2.76).value(), 3.0);
+  assert_eq!(Stars::from_rating(-1.0).value(), 0.0);
+  assert_eq!(Stars::from_rating(3.5).to_string(), "★★★½");
+  assert_eq!(readable_stat_name("numberOfEyes"), "Number of Eyes");
+
+  let mut player = Player::new(&mut Random::new(9), "Nepe Oobocu".to_string());
+  for stat in player.stats_mut() {
+    *stat = CharacterStat::new(2.0);
+  }
+  player.hit_points = CharacterStat::new(4.5);
+  player.splash = CharacterStat::new(0.25);
+
+  let report = ScoutingReport::new(&player);
+  assert_eq!(report.best, Attribute { name: "Hit Points".to_string(), value: 4.5 });
+  assert_eq!(report.worst, Attribute { name: "Splash".to_string(), value: 0.25 });
+  assert_eq!(report.pitching.value(), 2.0);
+  let text = report.to_string();
+  assert!(text.starts_with("Nepe Oobocu\nSoulscream: "));
+  assert!(text.contains("\nPitching     ★★     (2)\n"));
+  assert!(text.ends_with("Worst: Splash (0.25)"));
+}
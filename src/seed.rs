@@ -0,0 +1,138 @@
+//! Seeds for everything random, all worked out from one number.
+//!
+//! A league has a master seed. Each season gets its own seed derived from
+//! that one, each day from the season's, each game from the day's, and the
+//! parts of a game that roll on their own (weather, names, flavor) from the
+//! game's. Every step mixes the bits properly, so nearby numbers (day 1 and
+//! day 2, or game 0 and game 1) end up with seeds that have nothing to do
+//! with each other, and a whole season can be played again from its master
+//! seed.
+//!
+//! ```
+//! use alt_league_blaseball::seed::{Seed, Subsystem};
+//!
+//! let game = Seed::new(42).season(1).day(3).game(0);
+//! assert_ne!(game, Seed::new(42).season(1).day(3).game(1));
+//! assert_eq!(game.subsystem(Subsystem::Weather), Seed::new(42).season(1).day(3).game(0).subsystem(Subsystem::Weather));
+//! ```
+
+use crate::java_random::Random;
+
+// Keeps each level apart, so season 3 day 1 isn't the same as season 1 day 3.
+const SEASON: u64 = 0x5EA5_0000_0000_0001;
+const DAY: u64 = 0xDA7_0000_0000_0002;
+const GAME: u64 = 0x6A3E_0000_0000_0003;
+const MATCHUP: u64 = 0x3A7C_0000_0000_0004;
+const SUBSYSTEM: u64 = 0x5B5_0000_0000_0005;
+
+pub(crate) const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// The parts of the simulation that get a stream of their own, so adding a
+/// roll to one doesn't change what happens in the others. Everything else
+/// in a game rolls with the game's own seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    Weather,
+    Names,
+    Flavor, // Rituals, coffee, blood types and fates
+    Teams, // Generating teams and their rosters
+}
+
+impl Subsystem {
+    fn index(&self) -> u64 {
+        match self {
+            Subsystem::Weather => 0,
+            Subsystem::Names => 1,
+            Subsystem::Flavor => 2,
+            Subsystem::Teams => 3,
+        }
+    }
+}
+
+/// A seed somewhere in the hierarchy. Deriving never changes the seed it's
+/// derived from, so the same path always leads to the same seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Seed(i64);
+
+impl Seed {
+    pub fn new(seed: i64) -> Self {
+        Seed(seed)
+    }
+
+    pub fn value(&self) -> i64 {
+        self.0
+    }
+
+    /// A `Random` seeded with this seed.
+    pub fn rng(&self) -> Random {
+        Random::new(self.0)
+    }
+
+    pub fn season(&self, season: usize) -> Self {
+        self.derive(SEASON, season as u64)
+    }
+
+    pub fn day(&self, day: usize) -> Self {
+        self.derive(DAY, day as u64)
+    }
+
+    /// The seed for the `index`th game of the day.
+    pub fn game(&self, index: usize) -> Self {
+        self.derive(GAME, index as u64)
+    }
+
+    /// The seed for a game between two teams, for when there's no schedule
+    /// to say which game of the day it is. Swapping home and away gives a
+    /// different seed.
+    pub fn matchup(&self, home: u32, away: u32) -> Self {
+        self.derive(MATCHUP, (home as u64) << 32 | away as u64)
+    }
+
+    pub fn subsystem(&self, subsystem: Subsystem) -> Self {
+        self.derive(SUBSYSTEM, subsystem.index())
+    }
+
+    fn derive(&self, level: u64, index: u64) -> Self {
+        let parent = mix(self.0 as u64 ^ level);
+        Seed(mix(parent.wrapping_add(index.wrapping_add(1).wrapping_mul(GOLDEN_GAMMA))) as i64)
+    }
+}
+
+/// Scrambles the bits of `value` (the SplitMix64 finalizer). Every bit of
+/// the input has an even chance of flipping every bit of the output.
+pub fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[test]
+fn test_seed_hierarchy() {
+    use std::collections::BTreeSet;
+
+    let master = Seed::new(7);
+    assert_eq!(master.season(2).day(5).game(1), Seed::new(7).season(2).day(5).game(1));
+    assert_ne!(master.season(1).day(3), master.season(3).day(1));
+    assert_ne!(master.day(1), master.game(1));
+    assert_ne!(master.matchup(1, 2), master.matchup(2, 1));
+
+    // No two games in a season, or parts of a game, share a seed
+    let mut seeds = BTreeSet::new();
+    for season in 0..3 {
+        for day in 1..=99 {
+            for game in 0..10 {
+                let seed = master.season(season).day(day).game(game);
+                assert!(seeds.insert(seed.value()));
+                for subsystem in [Subsystem::Weather, Subsystem::Names, Subsystem::Flavor, Subsystem::Teams] {
+                    assert!(seeds.insert(seed.subsystem(subsystem).value()));
+                }
+            }
+        }
+    }
+
+    // Neighbouring seeds look nothing alike
+    let flipped = (master.day(1).value() ^ master.day(2).value()).count_ones();
+    assert!((16..=48).contains(&flipped), "{}", flipped);
+}
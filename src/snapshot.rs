@@ -0,0 +1,157 @@
+//! Snapshots of games in progress, for picking a game back up in another
+//! process exactly where it stopped.
+//!
+//! A snapshot is JSON with a format name and version wrapped around the
+//! whole `Game`: inning, count, outs, bases, batting order positions,
+//! pitchers, scores, the messages that haven't been read yet, and the
+//! exact state of the RNG. Restoring it and stepping on gives the same
+//! game, pitch for pitch, as if it never stopped.
+//!
+//! ```
+//! use alt_league_blaseball::game::Game;
+//! use alt_league_blaseball::java_random::Random;
+//! use alt_league_blaseball::rules::Rules;
+//! use alt_league_blaseball::team::Team;
+//!
+//! let mut rng = Random::new(1);
+//! let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules::default());
+//! for _ in 0..100 {
+//!     game.step();
+//! }
+//! let snapshot = game.snapshot();
+//! let mut restored = Game::restore(&snapshot).unwrap();
+//! assert_eq!(restored.step(), game.step());
+//! ```
+
+use std::fmt;
+
+use crate::game::Game;
+use crate::rules::RulesError;
+
+/// Identifies a snapshot, in case one ends up somewhere it shouldn't.
+pub const FORMAT: &str = "albtrs-game-snapshot";
+/// Bumped whenever a change to `Game` means older versions can't read a snapshot.
+/// Restoring takes any version up to this one.
+///
+/// 2: The game and weather RNGs can be any `RngBackend`, not just a `Random`.
+pub const VERSION: u32 = 2;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Json(serde_json::Error), // Not JSON, or not a game
+    WrongFormat(String), // The format it says it is
+    UnsupportedVersion(u32), // Newer than this version knows how to read
+    InvalidRules(RulesError), // A game can't be played by the rules it has
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Json(error) => write!(f, "invalid snapshot: {}", error),
+            SnapshotError::WrongFormat(format) => write!(f, "not a game snapshot: {:?}", format),
+            SnapshotError::UnsupportedVersion(version) => write!(f, "snapshot version {} is newer than {}", version, VERSION),
+            SnapshotError::InvalidRules(error) => write!(f, "invalid rules in snapshot: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Json(error) => Some(error),
+            SnapshotError::InvalidRules(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(error: serde_json::Error) -> Self {
+        SnapshotError::Json(error)
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SnapshotRef<'a> {
+    format: &'a str,
+    version: u32,
+    game: &'a Game,
+}
+
+// Read in two passes so the version is checked before the
+// game, which might not make sense to this version.
+#[derive(serde::Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+}
+
+#[derive(serde::Deserialize)]
+struct Snapshot {
+    game: Game,
+}
+
+impl Game {
+    /// Saves the whole game as it is right now. See the `snapshot` module.
+    pub fn snapshot(&self) -> String {
+        let snapshot = SnapshotRef {
+            format: FORMAT,
+            version: VERSION,
+            game: self,
+        };
+        serde_json::to_string(&snapshot).expect("games always serialize")
+    }
+
+    /// Loads a game saved by `snapshot()`, ready to carry on stepping.
+    pub fn restore(snapshot: &str) -> Result<Game, SnapshotError> {
+        let header: Header = serde_json::from_str(snapshot)?;
+        if header.format != FORMAT {
+            return Err(SnapshotError::WrongFormat(header.format));
+        }
+        if header.version > VERSION {
+            return Err(SnapshotError::UnsupportedVersion(header.version));
+        }
+        let snapshot: Snapshot = serde_json::from_str(snapshot)?;
+        snapshot.game.rules.validate().map_err(SnapshotError::InvalidRules)?;
+        Ok(snapshot.game)
+    }
+}
+
+#[test]
+fn test_snapshot_resumes_exactly() {
+    use crate::java_random::Random;
+    use crate::rng::GameRng;
+    use crate::rules::Rules;
+    use crate::team::Team;
+
+    let mut rng = Random::new(21);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 5, 0, Rules::default());
+    while game.inning < 4 || game.bases.iter().all(Option::is_none) {
+        game.step();
+    }
+    let restored = Game::restore(&game.snapshot()).unwrap();
+    assert_eq!((restored.inning, restored.top, restored.balls, restored.strikes, restored.outs), (game.inning, game.top, game.balls, game.strikes, game.outs));
+    assert_eq!((restored.home_bat, restored.away_bat), (game.home_bat, game.away_bat));
+    assert_eq!(restored.bases, game.bases);
+    assert_eq!(restored.message_log.messages, game.message_log.messages);
+
+    let mut restored = restored;
+    game.simulate_game();
+    restored.simulate_game();
+    assert_eq!(restored.events, game.events);
+    assert_eq!(restored.message_log.messages, game.message_log.messages);
+    assert_eq!(restored.rng.next_i64(), game.rng.next_i64());
+
+    // Version 1 only had Java RNGs, which still load the same
+    let older = game.snapshot().replacen(&format!("\"version\":{}", VERSION), "\"version\":1", 1);
+    assert_eq!(Game::restore(&older).unwrap().events, game.events);
+
+    let newer = game.snapshot().replacen(&format!("\"version\":{}", VERSION), &format!("\"version\":{}", VERSION + 1), 1);
+    assert!(matches!(Game::restore(&newer), Err(SnapshotError::UnsupportedVersion(version)) if version == VERSION + 1));
+    assert!(matches!(Game::restore(r#"{"format":"league","version":1}"#), Err(SnapshotError::WrongFormat(_))));
+    assert!(matches!(Game::restore("{"), Err(SnapshotError::Json(_))));
+
+    let mut no_outs: serde_json::Value = serde_json::from_str(&game.snapshot()).unwrap();
+    no_outs["game"]["rules"]["outs"] = 0.into();
+    assert!(matches!(Game::restore(&no_outs.to_string()), Err(SnapshotError::InvalidRules(RulesError::TooLow("outs")))));
+}
@@ -0,0 +1,355 @@
+// Everything that gets counted during games lives here. The counts are
+// built from the Events a Game emits, so they never have to be updated
+// by hand inside the game logic.
+
+use std::collections::BTreeMap;
+
+use crate::event::{Event, EventKind, PlayerId, TeamId};
+use crate::rules::Rules;
+
+// Used for ERA and K/9
+const INNINGS_PER_GAME: f64 = 9.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Statistic {
+  // Batting
+  PlateAppearances,
+  Hits(usize), // Bases hit
+  Walks,
+  StrikeoutsLooking,
+  StrikeoutsSwinging,
+  Flyouts,
+  Groundouts,
+  Steals,
+  CaughtStealing,
+  Runs,
+
+  // Pitching
+  Pitches,
+  Balls,
+  Strikes,
+  OutsRecorded,
+  RunsAllowed,
+  HitsAllowed,
+  WalksAllowed,
+  Strikeouts,
+  Wins,
+  Losses,
+}
+
+/// One player's counting stats, either for a single game or a whole season.
+// Stored as a list of pairs when serialized, since stats like Hits(2) can't be map keys in formats like JSON.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "Vec<(Statistic, f64)>", from = "Vec<(Statistic, f64)>"))]
+pub struct Statistics {
+  values: BTreeMap<Statistic, f64>,
+}
+
+impl Statistics {
+  pub fn new() -> Self {
+    Statistics {
+      values: BTreeMap::new(),
+    }
+  }
+
+  pub fn add(&mut self, statistic: Statistic) {
+    self.add_with_value(statistic, 1.0);
+  }
+
+  pub fn add_with_value(&mut self, statistic: Statistic, value: f64) {
+    *self.values.entry(statistic).or_insert(0.0) += value;
+  }
+
+  /// Adds every stat in `other` on top of these ones.
+  pub fn merge(&mut self, other: &Statistics) {
+    for (statistic, value) in other.iter() {
+      self.add_with_value(statistic, value);
+    }
+  }
+
+  pub fn get(&self, statistic: Statistic) -> f64 {
+    self.values.get(&statistic).copied().unwrap_or(0.0)
+  }
+
+  /// Iterates over every stat that's been recorded, in order.
+  pub fn iter(&self) -> impl Iterator<Item = (Statistic, f64)> + '_ {
+    self.values.iter().map(|(statistic, value)| (*statistic, *value))
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+
+  pub fn clear(&mut self) {
+    self.values.clear();
+  }
+
+  pub fn hits(&self) -> f64 {
+    self.iter()
+      .filter(|(statistic, _)| matches!(statistic, Statistic::Hits(_)))
+      .map(|(_, value)| value)
+      .sum()
+  }
+
+  /// A home run counts as one more base than the rules have, so four with the default rules.
+  pub fn total_bases(&self) -> f64 {
+    self.iter()
+      .filter_map(|(statistic, value)| match statistic {
+        Statistic::Hits(bases) => Some(bases as f64 * value),
+        _ => None,
+      })
+      .sum()
+  }
+
+  /// Times the player struck out at the plate. `Statistic::Strikeouts` is
+  /// for strikeouts they pitched.
+  pub fn strikeouts(&self) -> f64 {
+    self.get(Statistic::StrikeoutsLooking) + self.get(Statistic::StrikeoutsSwinging)
+  }
+
+  /// Every plate appearance that didn't end in a walk.
+  pub fn at_bats(&self) -> f64 {
+    self.get(Statistic::PlateAppearances) - self.get(Statistic::Walks)
+  }
+
+  /// Outs recorded, counted in innings of however many outs the rules have.
+  pub fn innings_pitched(&self, rules: &Rules) -> f64 {
+    self.get(Statistic::OutsRecorded) / rules.outs as f64
+  }
+
+  // Rate stats are None when there's nothing to divide by yet.
+
+  pub fn batting_average(&self) -> Option<f64> {
+    ratio(self.hits(), self.at_bats())
+  }
+
+  pub fn on_base_percentage(&self) -> Option<f64> {
+    ratio(self.hits() + self.get(Statistic::Walks), self.get(Statistic::PlateAppearances))
+  }
+
+  pub fn slugging(&self) -> Option<f64> {
+    ratio(self.total_bases(), self.at_bats())
+  }
+
+  pub fn earned_run_average(&self, rules: &Rules) -> Option<f64> {
+    ratio(self.get(Statistic::RunsAllowed) * INNINGS_PER_GAME, self.innings_pitched(rules))
+  }
+
+  pub fn whip(&self, rules: &Rules) -> Option<f64> {
+    ratio(self.get(Statistic::WalksAllowed) + self.get(Statistic::HitsAllowed), self.innings_pitched(rules))
+  }
+
+  pub fn strikeouts_per_nine(&self, rules: &Rules) -> Option<f64> {
+    ratio(self.get(Statistic::Strikeouts) * INNINGS_PER_GAME, self.innings_pitched(rules))
+  }
+}
+
+impl From<Vec<(Statistic, f64)>> for Statistics {
+  fn from(values: Vec<(Statistic, f64)>) -> Self {
+    Statistics {
+      values: values.into_iter().collect(),
+    }
+  }
+}
+
+impl From<Statistics> for Vec<(Statistic, f64)> {
+  fn from(statistics: Statistics) -> Self {
+    statistics.iter().collect()
+  }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> Option<f64> {
+  if denominator > 0.0 {
+    Some(numerator / denominator)
+  } else {
+    None
+  }
+}
+
+/// The stats of every player that took part in a game, built up one
+/// `Event` at a time.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatSheet {
+  players: BTreeMap<PlayerId, Statistics>,
+  pitchers: BTreeMap<TeamId, PlayerId>, // Whoever last pitched for each team
+}
+
+impl StatSheet {
+  pub fn new() -> Self {
+    StatSheet {
+      players: BTreeMap::new(),
+      pitchers: BTreeMap::new(),
+    }
+  }
+
+  pub fn from_events<'a>(events: impl IntoIterator<Item = &'a Event>) -> Self {
+    let mut sheet = StatSheet::new();
+    for event in events {
+      sheet.record(event);
+    }
+    sheet
+  }
+
+  /// Counts a single event.
+  ///
+  /// Every event that comes from a pitch (balls, strikes, fouls, and balls
+  /// in play) counts as a pitch thrown.
+  pub fn record(&mut self, event: &Event) {
+    self.pitchers.insert(event.pitching_team, event.pitcher);
+
+    let batter = event.batter;
+    let pitcher = event.pitcher;
+    match &event.kind {
+      EventKind::NextBatter => {
+        self.add(batter, Statistic::PlateAppearances);
+      },
+      EventKind::Ball => {
+        self.add_pitch(pitcher, Statistic::Balls);
+      },
+      EventKind::Walk => {
+        self.add_pitch(pitcher, Statistic::Balls);
+        self.add(pitcher, Statistic::WalksAllowed);
+        self.add(batter, Statistic::Walks);
+      },
+      EventKind::StrikeLooking | EventKind::StrikeSwinging | EventKind::FoulBall => {
+        self.add_pitch(pitcher, Statistic::Strikes);
+      },
+      EventKind::StruckOutLooking => {
+        self.add_pitch(pitcher, Statistic::Strikes);
+        self.add(pitcher, Statistic::Strikeouts);
+        self.add(batter, Statistic::StrikeoutsLooking);
+      },
+      EventKind::StruckOutSwinging => {
+        self.add_pitch(pitcher, Statistic::Strikes);
+        self.add(pitcher, Statistic::Strikeouts);
+        self.add(batter, Statistic::StrikeoutsSwinging);
+      },
+      EventKind::Flyout { .. } => {
+        self.add_pitch(pitcher, Statistic::Strikes);
+        self.add(batter, Statistic::Flyouts);
+      },
+      EventKind::Groundout { .. } => {
+        self.add_pitch(pitcher, Statistic::Strikes);
+        self.add(batter, Statistic::Groundouts);
+      },
+      EventKind::Hit { bases } => {
+        self.add_pitch(pitcher, Statistic::Strikes);
+        self.add(pitcher, Statistic::HitsAllowed);
+        self.add(batter, Statistic::Hits(*bases));
+      },
+      EventKind::Steal { runner, .. } => {
+        self.add(*runner, Statistic::Steals);
+      },
+      EventKind::CaughtStealing { runner, .. } => {
+        self.add(*runner, Statistic::CaughtStealing);
+      },
+      EventKind::Score { runner } => {
+        self.add(*runner, Statistic::Runs);
+        self.add(pitcher, Statistic::RunsAllowed);
+      },
+      EventKind::Out => {
+        self.add(pitcher, Statistic::OutsRecorded);
+      },
+      EventKind::GameOver { winner, loser } => {
+        if let Some(pitcher) = self.pitchers.get(winner).copied() {
+          self.add(pitcher, Statistic::Wins);
+        }
+        if let Some(pitcher) = self.pitchers.get(loser).copied() {
+          self.add(pitcher, Statistic::Losses);
+        }
+      },
+      _ => {},
+    }
+  }
+
+  pub fn get(&self, id: PlayerId) -> Option<&Statistics> {
+    self.players.get(&id)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (PlayerId, &Statistics)> {
+    self.players.iter().map(|(id, statistics)| (*id, statistics))
+  }
+
+  pub fn len(&self) -> usize {
+    self.players.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.players.is_empty()
+  }
+
+  fn add(&mut self, id: PlayerId, statistic: Statistic) {
+    self.players.entry(id).or_default().add(statistic);
+  }
+
+  fn add_pitch(&mut self, pitcher: PlayerId, statistic: Statistic) {
+    self.add(pitcher, Statistic::Pitches);
+    self.add(pitcher, statistic);
+  }
+}
+
+#[test]
+fn test_game_statistics_add_up() {
+  use crate::game::Game;
+  use crate::java_random::Random;
+  use crate::team::Team;
+
+  let mut rng = Random::new(2);
+  let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 3, 0, Rules::default());
+  game.simulate_game();
+
+  let sheet = &game.statistics;
+  assert_eq!(*sheet, StatSheet::from_events(game.events.iter()));
+
+  let total = |statistic: Statistic| sheet.iter().map(|(_, statistics)| statistics.get(statistic)).sum::<f64>();
+  assert_eq!(total(Statistic::Runs), game.scores.0 + game.scores.1);
+  assert_eq!(total(Statistic::Runs), total(Statistic::RunsAllowed));
+  assert_eq!(total(Statistic::Walks), total(Statistic::WalksAllowed));
+  assert_eq!(total(Statistic::Wins), 1.0);
+  assert_eq!(total(Statistic::Losses), 1.0);
+  assert_eq!(total(Statistic::Pitches), total(Statistic::Balls) + total(Statistic::Strikes));
+
+  // Every out of every inning was recorded by a pitcher
+  let outs = game.events.iter().filter(|event| event.kind == EventKind::Out).count();
+  assert_eq!(total(Statistic::OutsRecorded), outs as f64);
+
+  // And the game's stats made it onto the players' seasons
+  for player in game.home.players().chain(game.away.players()) {
+    assert_eq!(sheet.get(player.id).cloned().unwrap_or_default(), *player.get_statistics());
+  }
+}
+
+#[test]
+fn test_rate_statistics() {
+  let rules = Rules::default();
+  let mut statistics = Statistics::new();
+  statistics.add_with_value(Statistic::PlateAppearances, 10.0);
+  statistics.add_with_value(Statistic::Walks, 2.0);
+  statistics.add_with_value(Statistic::Hits(1), 2.0);
+  statistics.add(Statistic::Hits(4));
+  statistics.add(Statistic::Hits(5)); // A home run with a fourth base
+
+  assert_eq!(statistics.at_bats(), 8.0);
+  assert_eq!(statistics.batting_average(), Some(0.5));
+  assert_eq!(statistics.on_base_percentage(), Some(0.6));
+  assert_eq!(statistics.slugging(), Some(11.0 / 8.0));
+  assert_eq!(statistics.earned_run_average(&rules), None);
+
+  statistics.add_with_value(Statistic::OutsRecorded, 27.0);
+  statistics.add_with_value(Statistic::RunsAllowed, 3.0);
+  statistics.add_with_value(Statistic::Strikeouts, 9.0);
+  statistics.add_with_value(Statistic::HitsAllowed, 5.0);
+  statistics.add_with_value(Statistic::WalksAllowed, 4.0);
+  assert_eq!(statistics.earned_run_average(&rules), Some(3.0));
+  assert_eq!(statistics.whip(&rules), Some(1.0));
+  assert_eq!(statistics.strikeouts_per_nine(&rules), Some(9.0));
+
+  // Innings are as long as the rules say
+  let two_outs = Rules { outs: 2, ..rules };
+  assert_eq!(statistics.innings_pitched(&rules), 9.0);
+  assert_eq!(statistics.innings_pitched(&two_outs), 13.5);
+  assert_eq!(statistics.earned_run_average(&two_outs), Some(2.0));
+}
@@ -1,10 +1,113 @@
 // So the goal of this file is to do all team- and player-related JSON parsing.
 //use json_library; // for when we decide to import one
 
-use crate::java_random::Random;
+use std::fmt;
+
+use crate::rng::GameRng;
+use crate::modifiers::{Modifier, ModifierDuration, ModifierKind};
 use crate::player::Player;
+use crate::seed::mix;
+
+pub const MIN_LINEUP: usize = 1; // Someone has to bat
+pub const MIN_ROTATION: usize = 1; // Someone has to pitch
+
+/// One of the lists of players on a team.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Roster {
+  Lineup, // Bats, in order
+  Rotation, // Pitches, taking turns by day
+  Shadows, // On the team, but doesn't play
+}
+
+impl Roster {
+  /// The fewest players the list can have.
+  pub fn minimum(&self) -> usize {
+    match self {
+      Roster::Lineup => MIN_LINEUP,
+      Roster::Rotation => MIN_ROTATION,
+      Roster::Shadows => 0,
+    }
+  }
+}
+
+/// Why a roster change wasn't allowed. The team is left as it was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RosterError {
+  DuplicatePlayer(u32), // Player ID that's already on the team
+  UnknownPlayer(u32), // Player ID that isn't on the team
+  OutOfRange(Roster, usize), // Index past the end of the list
+  TooFewPlayers(Roster), // The list would go under its minimum
+}
+
+impl fmt::Display for RosterError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      RosterError::DuplicatePlayer(id) => write!(f, "player {} is already on the team", id),
+      RosterError::UnknownPlayer(id) => write!(f, "player {} isn't on the team", id),
+      RosterError::OutOfRange(roster, index) => write!(f, "there's no spot {} in the {:?}", index, roster),
+      RosterError::TooFewPlayers(roster) => write!(f, "the {:?} needs at least {} players", roster, roster.minimum()),
+    }
+  }
+}
+
+impl std::error::Error for RosterError {}
+
+/// How a team orders its lineup before each game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineupStrategy {
+  /// Bats in whatever order the lineup's in.
+  #[default]
+  Manual,
+  /// Best `Player::get_batting()` first.
+  Batting,
+  /// Best on-base percentage this season first. Anyone who hasn't been up to bat yet goes last.
+  OnBasePercentage,
+  /// A new random order every game, drawn from the game's RNG.
+  Shuffle,
+}
+
+const LINEUP_STRATEGIES: [LineupStrategy; 4] = [LineupStrategy::Manual, LineupStrategy::Batting, LineupStrategy::OnBasePercentage, LineupStrategy::Shuffle];
+
+impl LineupStrategy {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      LineupStrategy::Manual => "manual",
+      LineupStrategy::Batting => "batting",
+      LineupStrategy::OnBasePercentage => "obp",
+      LineupStrategy::Shuffle => "shuffle",
+    }
+  }
+
+  /// Finds the strategy with the given name, as returned by `as_str()`.
+  pub fn from_name(name: &str) -> Option<LineupStrategy> {
+    LINEUP_STRATEGIES.iter().copied().find(|strategy| strategy.as_str() == name)
+  }
+
+  /// Puts `lineup` in batting order. Only `Shuffle` uses `rng`.
+  pub fn order(&self, lineup: &mut [Player], rng: &mut impl GameRng) {
+    match self {
+      LineupStrategy::Manual => {},
+      // Sorts are stable, so ties keep their manual order
+      LineupStrategy::Batting => lineup.sort_by(|a, b| b.get_batting().total_cmp(&a.get_batting())),
+      LineupStrategy::OnBasePercentage => lineup.sort_by(|a, b| {
+        let obp = |player: &Player| player.get_statistics().on_base_percentage().unwrap_or(f64::NEG_INFINITY);
+        obp(b).total_cmp(&obp(a))
+      }),
+      // Same as Java's Collections.shuffle()
+      LineupStrategy::Shuffle => {
+        for i in (1..lineup.len()).rev() {
+          let j = rng.next_u32_bound(i as u32 + 1) as usize;
+          lineup.swap(i, j);
+        }
+      },
+    }
+  }
+}
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Team {
   pub name: String,
   pub location: String,
@@ -13,6 +116,8 @@ pub struct Team {
 
   lineup: Vec<Player>,
   rotation: Vec<Player>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  shadows: Vec<Player>,
 
   non_losses: u32, // equivalent to alb's actualWins field
   wins: i32,
@@ -20,38 +125,57 @@ pub struct Team {
 
   favor: u32,
   id: u32,
+
+  #[cfg_attr(feature = "serde", serde(default))]
+  lineup_strategy: LineupStrategy,
+
+  #[cfg_attr(feature = "serde", serde(default))]
+  modifiers: Vec<Modifier>, // Apply to every player on the team
 }
 
 impl Team {
   /// Creates a new team with the given name, location, logo, abbreviation, and lineup.
   /// Generates a random lineup, and a random rotation.
-  pub fn new(rng: &mut Random, name: String, location: String, logo: String, favor: u32, abbreviation: String) -> Self {
+  pub fn new(rng: &mut impl GameRng, name: String, location: String, logo: String, favor: u32, abbreviation: String) -> Self {
+    let lineup = Team::generate_new_players(rng, 9);
+    let rotation = Team::generate_new_players(rng, 5);
+    Team::with_players(name, location, logo, favor, abbreviation, lineup, rotation)
+  }
+
+  /// Creates a new team out of players that already exist.
+  pub fn with_players(name: String, location: String, logo: String, favor: u32, abbreviation: String, lineup: Vec<Player>, rotation: Vec<Player>) -> Self {
     Team {
+      id: Team::id_for(&lineup, &rotation),
+
       name,
       location,
       logo,
       abbreviation,
 
-      lineup: Team::generate_new_players(rng, 9),
-      rotation: Team::generate_new_players(rng, 5),
+      lineup,
+      rotation,
+      shadows: Vec::new(),
 
       non_losses: 0,
       wins: 0,
       losses: 0,
 
       favor,
-      id: 0, // Placeholder
+
+      lineup_strategy: LineupStrategy::default(),
+
+      modifiers: Vec::new(),
     }
   }
 
   /// Creates an empty team with the name "team", location "null", and other blank data.
-  pub fn empty(rng: &mut Random) -> Self {
+  pub fn empty(rng: &mut impl GameRng) -> Self {
     Team::new(rng, "team".to_string(), "null".to_string(), "❓".to_string(), 0, "NULL".to_string())
   }
 
-  pub fn generate_new_players(rng: &mut Random, length: usize) -> Vec<Player> {
+  pub fn generate_new_players(rng: &mut impl GameRng, length: usize) -> Vec<Player> {
     let mut players: Vec<Player> = Vec::new();
-    for i in 0..length {
+    for _i in 0..length {
       players.push(Player::default(rng));
     }
     players
@@ -59,9 +183,9 @@ impl Team {
   
   pub fn get_team_name(&self) -> String {
     if let Some(location) = &self.get_location() {
-      return format!("{} {}", self.get_name(), location);
+      format!("{} {}", self.get_name(), location)
     } else {
-      return self.get_name();
+      self.get_name()
     }
   }
 
@@ -71,22 +195,39 @@ impl Team {
 
   /// Returns the team's location. It's wrapped in an Option, since team locations can apparently be empty?
   pub fn get_location(&self) -> Option<String> {
-    if self.location == "" {
+    if self.location.is_empty() {
       None
     } else {
       Some(self.location.clone())
     }
   }
 
+  pub fn get_id(&self) -> u32 {
+    self.id
+  }
+
+  // Worked out from the players' ids instead of rolled, so making a team
+  // doesn't use up a roll from the RNG its players came from.
+  fn id_for(lineup: &[Player], rotation: &[Player]) -> u32 {
+    lineup.iter().chain(rotation).fold(0, |hash, player| mix(hash ^ player.id as u64)) as u32
+  }
+
+  /// Iterates over every player on the team without cloning them, shadows included.
+  pub fn players(&self) -> impl Iterator<Item = &Player> {
+    self.lineup.iter().chain(self.rotation.iter()).chain(self.shadows.iter())
+  }
+
+  pub fn players_mut(&mut self) -> impl Iterator<Item = &mut Player> {
+    self.lineup.iter_mut().chain(self.rotation.iter_mut()).chain(self.shadows.iter_mut())
+  }
+
+  /// Iterates over the players who play for the team, the lineup and then the rotation, leaving out shadows.
+  pub fn active_players(&self) -> impl Iterator<Item = &Player> {
+    self.lineup.iter().chain(self.rotation.iter())
+  }
+
   pub fn get_active_players(&self) -> Vec<Player> {
-    let mut active_players: Vec<Player> = Vec::new();
-    for player in &self.lineup {
-      active_players.push(player.clone());
-    }
-    for player in &self.rotation {
-      active_players.push(player.clone());
-    }
-    active_players
+    self.active_players().cloned().collect()
   }
 
   pub fn get_active_pitchers(&self) -> Vec<Player> {
@@ -105,6 +246,144 @@ impl Team {
     active_batters
   }
 
+  pub fn get_lineup_strategy(&self) -> LineupStrategy {
+    self.lineup_strategy
+  }
+
+  pub fn set_lineup_strategy(&mut self, strategy: LineupStrategy) {
+    self.lineup_strategy = strategy;
+  }
+
+  /// Reorders the lineup with the team's `LineupStrategy`. Games do this when they start.
+  pub fn order_lineup(&mut self, rng: &mut impl GameRng) {
+    self.lineup_strategy.order(&mut self.lineup, rng);
+  }
+
+  pub fn get_shadows(&self) -> Vec<Player> {
+    self.shadows.clone()
+  }
+
+  /// Swaps the player with the given id for `replacement`, keeping their spot in the lineup, rotation or shadows.
+  /// Returns the player that was replaced, or `None` if they aren't on this team.
+  pub fn replace_player(&mut self, id: u32, replacement: Player) -> Option<Player> {
+    let slot = self.players_mut().find(|player| player.id == id)?;
+    Some(std::mem::replace(slot, replacement))
+  }
+
+  // Roster management. Every change checks that nobody's on the
+  // team twice and that the lineup and rotation don't run dry.
+
+  /// The players in one of the team's lists, in order.
+  pub fn roster(&self, roster: Roster) -> &[Player] {
+    match roster {
+      Roster::Lineup => &self.lineup,
+      Roster::Rotation => &self.rotation,
+      Roster::Shadows => &self.shadows,
+    }
+  }
+
+  fn roster_mut(&mut self, roster: Roster) -> &mut Vec<Player> {
+    match roster {
+      Roster::Lineup => &mut self.lineup,
+      Roster::Rotation => &mut self.rotation,
+      Roster::Shadows => &mut self.shadows,
+    }
+  }
+
+  /// Where the player with the given id is on the team.
+  pub fn find_player(&self, id: u32) -> Option<(Roster, usize)> {
+    [Roster::Lineup, Roster::Rotation, Roster::Shadows]
+      .into_iter()
+      .find_map(|roster| {
+        self.roster(roster)
+          .iter()
+          .position(|player| player.id == id)
+          .map(|index| (roster, index))
+      })
+  }
+
+  /// Adds a player to the end of one of the team's lists.
+  pub fn add_player(&mut self, roster: Roster, player: Player) -> Result<(), RosterError> {
+    let index = self.roster(roster).len();
+    self.insert_player(roster, index, player)
+  }
+
+  /// Adds a player to one of the team's lists, at `index`.
+  pub fn insert_player(&mut self, roster: Roster, index: usize, player: Player) -> Result<(), RosterError> {
+    if self.find_player(player.id).is_some() {
+      return Err(RosterError::DuplicatePlayer(player.id));
+    }
+    if index > self.roster(roster).len() {
+      return Err(RosterError::OutOfRange(roster, index));
+    }
+    self.roster_mut(roster).insert(index, player);
+    Ok(())
+  }
+
+  /// Takes a player off the team and returns them.
+  pub fn remove_player(&mut self, id: u32) -> Result<Player, RosterError> {
+    let (roster, index) = self.find_player(id).ok_or(RosterError::UnknownPlayer(id))?;
+    if self.roster(roster).len() <= roster.minimum() {
+      return Err(RosterError::TooFewPlayers(roster));
+    }
+    Ok(self.roster_mut(roster).remove(index))
+  }
+
+  /// Moves a player so they end up at spot `index` of `roster`. Works for
+  /// moving between lists as well as reordering one.
+  pub fn move_player(&mut self, id: u32, roster: Roster, index: usize) -> Result<(), RosterError> {
+    let (from, from_index) = self.find_player(id).ok_or(RosterError::UnknownPlayer(id))?;
+    if from != roster && self.roster(from).len() <= from.minimum() {
+      return Err(RosterError::TooFewPlayers(from));
+    }
+    let len = self.roster(roster).len();
+    if (from == roster && index >= len) || index > len {
+      return Err(RosterError::OutOfRange(roster, index));
+    }
+
+    let player = self.roster_mut(from).remove(from_index);
+    self.roster_mut(roster).insert(index, player);
+    Ok(())
+  }
+
+  /// Trades the spots of two players, wherever they are on the team.
+  pub fn swap_players(&mut self, first: u32, second: u32) -> Result<(), RosterError> {
+    let (first_roster, first_index) = self.find_player(first).ok_or(RosterError::UnknownPlayer(first))?;
+    let (second_roster, second_index) = self.find_player(second).ok_or(RosterError::UnknownPlayer(second))?;
+    if first_roster == second_roster {
+      self.roster_mut(first_roster).swap(first_index, second_index);
+    } else {
+      let first_player = self.roster(first_roster)[first_index].clone();
+      let second_player = std::mem::replace(&mut self.roster_mut(second_roster)[second_index], first_player);
+      self.roster_mut(first_roster)[first_index] = second_player;
+    }
+    Ok(())
+  }
+
+  pub fn add_modifier(&mut self, modifier: Modifier) {
+    self.modifiers.push(modifier);
+  }
+
+  pub fn has_modifier(&self, kind: ModifierKind) -> bool {
+    self.modifiers.iter().any(|modifier| modifier.kind == kind)
+  }
+
+  pub fn remove_modifier(&mut self, kind: ModifierKind) {
+    self.modifiers.retain(|modifier| modifier.kind != kind);
+  }
+
+  pub fn get_modifiers(&self) -> &[Modifier] {
+    &self.modifiers
+  }
+
+  /// Removes the modifiers that only last as long as `duration`, or less, from the team and everyone on it.
+  pub fn expire_modifiers(&mut self, duration: ModifierDuration) {
+    self.modifiers.retain(|modifier| modifier.outlasts(duration));
+    for player in self.players_mut() {
+      player.expire_modifiers(duration);
+    }
+  }
+
   pub fn get_wins(&self) -> i32 {
     self.wins
   }
@@ -117,6 +396,11 @@ impl Team {
     }
   }
 
+  /// Returns the number of games the team actually won, ignoring anything that changed their wins.
+  pub fn get_non_losses(&self) -> u32 {
+    self.non_losses
+  }
+
   pub fn get_losses(&self) -> i32 {
     self.losses
   }
@@ -129,10 +413,21 @@ impl Team {
     }
   }
 
+  pub fn set_favor(&mut self, favor: u32) {
+    self.favor = favor;
+  }
+
   pub fn get_favor(&self) -> u32 {
     self.favor
   }
 
+  /// Overwrites the team's standings, like when they're loaded from a save.
+  pub fn set_record(&mut self, wins: i32, losses: i32, non_losses: u32) {
+    self.wins = wins;
+    self.losses = losses;
+    self.non_losses = non_losses;
+  }
+
   pub fn add_win(&mut self) {
     self.wins += 1;
   }
@@ -158,4 +453,86 @@ impl PartialEq for Team {
   }
 }
 
-impl Eq for Team {}
\ No newline at end of file
+impl Eq for Team {}
+
+#[test]
+fn test_roster_management() {
+  use crate::java_random::Random;
+
+  let mut rng = Random::new(2);
+  let lineup = Team::generate_new_players(&mut rng, 3);
+  let rotation = Team::generate_new_players(&mut rng, 1);
+  let mut team = Team::with_players("Crabs".to_string(), "Baltimore".to_string(), "🦀".to_string(), 0, "BAL".to_string(), lineup, rotation);
+  let ids = |team: &Team, roster: Roster| -> Vec<u32> { team.roster(roster).iter().map(|player| player.id).collect() };
+  let lineup = ids(&team, Roster::Lineup);
+  let pitcher = ids(&team, Roster::Rotation)[0];
+
+  // Reordering
+  team.move_player(lineup[0], Roster::Lineup, 2).unwrap();
+  assert_eq!(ids(&team, Roster::Lineup), vec![lineup[1], lineup[2], lineup[0]]);
+  team.swap_players(lineup[1], lineup[0]).unwrap();
+  assert_eq!(ids(&team, Roster::Lineup), vec![lineup[0], lineup[2], lineup[1]]);
+  assert_eq!(team.move_player(lineup[0], Roster::Lineup, 3), Err(RosterError::OutOfRange(Roster::Lineup, 3)));
+
+  // Between lists
+  team.move_player(lineup[2], Roster::Shadows, 0).unwrap();
+  assert_eq!(team.find_player(lineup[2]), Some((Roster::Shadows, 0)));
+  assert_eq!(team.players().count(), 4);
+  assert_eq!(team.active_players().count(), 3);
+  assert!(!team.active_players().any(|player| player.id == lineup[2]));
+  team.swap_players(pitcher, lineup[2]).unwrap();
+  assert_eq!(ids(&team, Roster::Rotation), vec![lineup[2]]);
+  assert_eq!(team.get_active_pitchers()[0].id, lineup[2]);
+
+  // The rules
+  assert_eq!(team.move_player(lineup[2], Roster::Lineup, 0), Err(RosterError::TooFewPlayers(Roster::Rotation)));
+  assert_eq!(team.remove_player(lineup[2]).unwrap_err(), RosterError::TooFewPlayers(Roster::Rotation));
+  let duplicate = team.roster(Roster::Lineup)[0].clone();
+  assert_eq!(team.add_player(Roster::Shadows, duplicate), Err(RosterError::DuplicatePlayer(lineup[0])));
+  assert_eq!(team.remove_player(pitcher).unwrap().id, pitcher);
+  assert_eq!(team.remove_player(pitcher).unwrap_err(), RosterError::UnknownPlayer(pitcher));
+  team.add_player(Roster::Rotation, Player::default(&mut rng)).unwrap();
+  assert!(team.remove_player(lineup[2]).is_ok());
+}
+
+#[test]
+fn test_lineup_strategies() {
+  use crate::java_random::Random;
+  use crate::statistics::Statistic;
+
+  let mut rng = Random::new(6);
+  let mut team = Team::empty(&mut rng);
+  let manual: Vec<u32> = team.get_active_batters().iter().map(|player| player.id).collect();
+  team.order_lineup(&mut rng);
+  assert_eq!(team.get_active_batters().iter().map(|player| player.id).collect::<Vec<u32>>(), manual);
+
+  team.set_lineup_strategy(LineupStrategy::Batting);
+  team.order_lineup(&mut rng);
+  let batting: Vec<f64> = team.get_active_batters().iter().map(Player::get_batting).collect();
+  assert!(batting.windows(2).all(|pair| pair[0] >= pair[1]));
+
+  // Only one batter has been on base, and one's never been up
+  let [first, second, third] = [manual[3], manual[5], manual[7]];
+  for player in team.players_mut() {
+    if player.id == first || player.id == second {
+      player.add_statistic_with_value(Statistic::PlateAppearances, 4.0);
+    }
+    if player.id == first {
+      player.add_statistic(Statistic::Walks);
+    }
+  }
+  team.set_lineup_strategy(LineupStrategy::OnBasePercentage);
+  team.order_lineup(&mut rng);
+  let order: Vec<u32> = team.get_active_batters().iter().map(|player| player.id).collect();
+  assert_eq!(order[0], first);
+  assert_eq!(order[1], second);
+  assert!(order.iter().position(|id| *id == third).unwrap() > 1);
+
+  // Shuffles are drawn from the game's rng
+  team.set_lineup_strategy(LineupStrategy::Shuffle);
+  let mut again = team.clone();
+  team.order_lineup(&mut Random::new(1));
+  again.order_lineup(&mut Random::new(1));
+  assert_eq!(team.get_active_batters(), again.get_active_batters());
+  assert_eq!(LineupStrategy::from_name("obp"), Some(LineupStrategy::OnBasePercentage));
+}
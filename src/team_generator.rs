@@ -0,0 +1,168 @@
+// Makes up whole teams: a name, a place for them to play,
+// a logo, an abbreviation nobody else has, and a roster.
+
+use std::collections::BTreeSet;
+
+use crate::java_random::Random;
+use crate::name_generator::{Generator, NamingSystem};
+use crate::seed::{Seed, Subsystem};
+use crate::team::Team;
+
+const LINEUP_SIZE: usize = 9;
+const ROTATION_SIZE: usize = 5;
+
+// Team names, and the logo that goes with each one
+const NICKNAMES: [(&str, &str); 32] = [
+  ("Crabs", "🦀"), ("Bees", "🐝"), ("Firefighters", "🔥"), ("Moonwalkers", "🌙"),
+  ("Thunder", "⚡"), ("Eagles", "🦅"), ("Fish", "🐟"), ("Tacos", "🌮"),
+  ("Donuts", "🍩"), ("Planets", "🪐"), ("Owls", "🦉"), ("Snakes", "🐍"),
+  ("Wolves", "🐺"), ("Frogs", "🐸"), ("Octopi", "🐙"), ("Foxes", "🦊"),
+  ("Snails", "🐌"), ("Dragons", "🐉"), ("Comets", "☄️"), ("Mushrooms", "🍄"),
+  ("Anchors", "⚓"), ("Crowns", "👑"), ("Ghosts", "👻"), ("Robots", "🤖"),
+  ("Lobsters", "🦞"), ("Hot Peppers", "🌶️"), ("Cacti", "🌵"), ("Whales", "🐋"),
+  ("Sunbeams", "☀️"), ("Tigers", "🐅"), ("Roses", "🌹"), ("Breakfasts", "🥞"),
+];
+const ADJECTIVES: [&str; 12] = [
+  "Moist", "Electric", "Haunted", "Flying", "Spicy", "Sleepy",
+  "Cosmic", "Lucky", "Wild", "Gentle", "Furious", "Invisible",
+];
+const ADJECTIVE_CHANCE: f64 = 0.3;
+
+// Ways to turn a made up word into a place. `{}` is where the word goes.
+const PLACE_FORMS: [&str; 12] = [
+  "{}", "{}", "{}", "{}ville", "{}ton", "{} City",
+  "New {}", "Port {}", "{} Falls", "Lake {}", "San {}", "{} Springs",
+];
+
+/// Generates random teams. The same seed always generates the same teams,
+/// in the same order. Abbreviations are never handed out twice by the same
+/// generator.
+///
+/// ```
+/// use alt_league_blaseball::team_generator::TeamGenerator;
+///
+/// let mut generator = TeamGenerator::new(7);
+/// let teams: Vec<_> = (0..4).map(|_| generator.next_team()).collect();
+/// assert_eq!(teams[0].get_active_batters().len(), 9);
+/// ```
+pub struct TeamGenerator {
+  rng: Random,
+  names: Generator, // Comes up with the words places are named after
+  abbreviations: BTreeSet<String>, // Already taken
+}
+
+impl TeamGenerator {
+  pub fn new(seed: i64) -> Self {
+    TeamGenerator::with_naming_system(seed, NamingSystem::default())
+  }
+
+  /// Works the same as `new()`, but names places with the given `NamingSystem`.
+  pub fn with_naming_system(seed: i64, system: NamingSystem) -> Self {
+    let names = Generator::with_system(Seed::new(seed).subsystem(Subsystem::Names).value(), system);
+    TeamGenerator {
+      rng: Random::new(seed),
+      names,
+      abbreviations: BTreeSet::new(),
+    }
+  }
+
+  /// Marks an abbreviation as taken, so no generated team gets it.
+  /// Use it for teams that are already in the league.
+  pub fn reserve_abbreviation(&mut self, abbreviation: &str) {
+    self.abbreviations.insert(abbreviation.to_uppercase());
+  }
+
+  /// Generates a whole team, along with a lineup of 9 and a rotation of 5.
+  pub fn next_team(&mut self) -> Team {
+    let location = self.next_location();
+    let (name, logo) = self.next_name_and_logo();
+    let abbreviation = self.abbreviate(&location);
+    let favor = self.rng.next_u32_bound(100);
+    let lineup = Team::generate_new_players(&mut self.rng, LINEUP_SIZE);
+    let rotation = Team::generate_new_players(&mut self.rng, ROTATION_SIZE);
+    Team::with_players(name, location, logo, favor, abbreviation, lineup, rotation)
+  }
+
+  pub fn next_location(&mut self) -> String {
+    let word = self.names.next_name_with_distribution();
+    let form = PLACE_FORMS[self.rng.next_u32_bound(PLACE_FORMS.len() as u32) as usize];
+    form.replace("{}", &word)
+  }
+
+  pub fn next_name(&mut self) -> String {
+    self.next_name_and_logo().0
+  }
+
+  pub fn next_logo(&mut self) -> String {
+    self.next_name_and_logo().1
+  }
+
+  /// A team name, and the logo that goes with it.
+  pub fn next_name_and_logo(&mut self) -> (String, String) {
+    let (nickname, logo) = NICKNAMES[self.rng.next_u32_bound(NICKNAMES.len() as u32) as usize];
+    let name = if self.rng.next_f64() < ADJECTIVE_CHANCE {
+      let adjective = ADJECTIVES[self.rng.next_u32_bound(ADJECTIVES.len() as u32) as usize];
+      format!("{} {}", adjective, nickname)
+    } else {
+      nickname.to_string()
+    };
+    (name, logo.to_string())
+  }
+
+  /// Comes up with a three letter abbreviation for `location` that hasn't
+  /// been taken yet, and takes it. It's the first three letters if those
+  /// are free, otherwise the first letter and two of the others, like
+  /// `"NWY"` for New York once `"NEW"` is taken.
+  pub fn abbreviate(&mut self, location: &str) -> String {
+    let letters: Vec<char> = location.chars()
+      .filter(|c| c.is_alphabetic())
+      .flat_map(char::to_uppercase)
+      .collect();
+    let first = letters.first().copied().unwrap_or('X');
+
+    let mut candidates: Vec<String> = Vec::new();
+    for i in 1..letters.len() {
+      for j in i + 1..letters.len() {
+        candidates.push([first, letters[i], letters[j]].iter().collect());
+      }
+    }
+    // Numbered ones for when the letters run out
+    let stem: String = letters.iter().chain(['X', 'X'].iter()).take(2).collect();
+    candidates.extend((2..).map(|n| format!("{}{}", stem, n)).take(1000));
+
+    let abbreviation = candidates.into_iter()
+      .find(|candidate| !self.abbreviations.contains(candidate))
+      .expect("ran out of abbreviations");
+    self.abbreviations.insert(abbreviation.clone());
+    abbreviation
+  }
+}
+
+#[test]
+fn test_team_generator() {
+  let teams: Vec<Team> = {
+    let mut generator = TeamGenerator::new(11);
+    (0..40).map(|_| generator.next_team()).collect()
+  };
+  let again: Vec<Team> = {
+    let mut generator = TeamGenerator::new(11);
+    (0..40).map(|_| generator.next_team()).collect()
+  };
+
+  let abbreviations: BTreeSet<&String> = teams.iter().map(|team| &team.abbreviation).collect();
+  assert_eq!(abbreviations.len(), teams.len());
+  for (team, other) in teams.iter().zip(again.iter()) {
+    assert_eq!(team.get_team_name(), other.get_team_name());
+    assert_eq!(team.get_id(), other.get_id());
+    assert_eq!(team.abbreviation.chars().count(), 3);
+    assert!(NICKNAMES.iter().any(|(nickname, logo)| team.name.ends_with(nickname) && team.logo == *logo));
+    assert_eq!(team.get_active_batters().len(), LINEUP_SIZE);
+    assert_eq!(team.get_active_pitchers().len(), ROTATION_SIZE);
+  }
+
+  let mut generator = TeamGenerator::new(0);
+  generator.reserve_abbreviation("bal");
+  assert_eq!(generator.abbreviate("Baltimore"), "BAT");
+  assert_eq!(generator.abbreviate("Baltimore"), "BAI");
+  assert_eq!(generator.abbreviate("Al"), "AL2");
+}
@@ -1 +1,2 @@
-pub type EntityId = u64;
\ No newline at end of file
+/// Ids used to look up teams and players stored in a `League`.
+pub type EntityId = u32;
@@ -0,0 +1,322 @@
+//! JavaScript bindings, built with the `wasm` feature.
+//!
+//! ```js
+//! import init, { Team, Game } from "alt_league_blaseball";
+//!
+//! await init();
+//! const home = new Team(1n, "Crabs", "Baltimore", "🦀", 0, "BAL");
+//! const away = Team.empty(2n);
+//! const game = new Game(home, away, 1);
+//! while (!game.isOver) {
+//!     const events = game.step();
+//!     for (const line of game.messages()) {
+//!         console.log(line);
+//!     }
+//! }
+//! ```
+//!
+//! Players, teams and events are handed to JavaScript as plain objects,
+//! shaped the same way the `serde` feature serializes them.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::clock::{Clock, SystemClock};
+use crate::game::Game;
+use crate::java_random::Random;
+use crate::player::Player;
+use crate::rules::Rules;
+use crate::scouting::ScoutingReport;
+use crate::team::Team;
+
+// Maps become plain objects, and ids and seeds that don't fit in a JS number become BigInts.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    let serializer = serde_wasm_bindgen::Serializer::new()
+        .serialize_maps_as_objects(true)
+        .serialize_large_number_types_as_bigints(true);
+    value.serialize(&serializer).map_err(|error| JsError::new(&error.to_string()))
+}
+
+#[wasm_bindgen(js_name = Player)]
+pub struct WasmPlayer {
+    player: Player,
+}
+
+#[wasm_bindgen(js_class = Player)]
+impl WasmPlayer {
+    /// Generates a random player with a random name.
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: i64) -> WasmPlayer {
+        WasmPlayer {
+            player: Player::default(&mut Random::new(seed)),
+        }
+    }
+
+    #[wasm_bindgen(js_name = withName)]
+    pub fn with_name(seed: i64, name: String) -> WasmPlayer {
+        WasmPlayer {
+            player: Player::new(&mut Random::new(seed), name),
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.player.get_name()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> u32 {
+        self.player.id
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn batting(&self) -> f64 {
+        self.player.get_batting()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn pitching(&self) -> f64 {
+        self.player.get_pitching()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn baserunning(&self) -> f64 {
+        self.player.get_baserunning()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn defense(&self) -> f64 {
+        self.player.get_defense()
+    }
+
+    /// Returns the whole player as a plain object.
+    #[wasm_bindgen(js_name = toObject)]
+    pub fn to_object(&self) -> Result<JsValue, JsError> {
+        to_js(&self.player)
+    }
+
+    /// Returns the player's star ratings, best and worst stats, and flavor as a plain object.
+    #[wasm_bindgen(js_name = scoutingReport)]
+    pub fn scouting_report(&self) -> Result<JsValue, JsError> {
+        to_js(&ScoutingReport::new(&self.player))
+    }
+}
+
+#[wasm_bindgen(js_name = Team)]
+pub struct WasmTeam {
+    team: Team,
+}
+
+#[wasm_bindgen(js_class = Team)]
+impl WasmTeam {
+    /// Creates a team with a randomly generated lineup and rotation.
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: i64, name: String, location: String, logo: String, favor: u32, abbreviation: String) -> WasmTeam {
+        WasmTeam {
+            team: Team::new(&mut Random::new(seed), name, location, logo, favor, abbreviation),
+        }
+    }
+
+    pub fn empty(seed: i64) -> WasmTeam {
+        WasmTeam {
+            team: Team::empty(&mut Random::new(seed)),
+        }
+    }
+
+    /// Creates a team out of players that already exist.
+    #[wasm_bindgen(js_name = withPlayers)]
+    pub fn with_players(name: String, location: String, logo: String, favor: u32, abbreviation: String, lineup: Vec<WasmPlayer>, rotation: Vec<WasmPlayer>) -> WasmTeam {
+        let lineup = lineup.into_iter().map(|player| player.player).collect();
+        let rotation = rotation.into_iter().map(|player| player.player).collect();
+        WasmTeam {
+            team: Team::with_players(name, location, logo, favor, abbreviation, lineup, rotation),
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.team.get_name()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> u32 {
+        self.team.get_id()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn wins(&self) -> i32 {
+        self.team.get_wins()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn losses(&self) -> i32 {
+        self.team.get_losses()
+    }
+
+    /// Returns every player on the team as plain objects, lineup first.
+    pub fn players(&self) -> Result<JsValue, JsError> {
+        to_js(&self.team.players().collect::<Vec<&Player>>())
+    }
+
+    /// Returns the whole team as a plain object.
+    #[wasm_bindgen(js_name = toObject)]
+    pub fn to_object(&self) -> Result<JsValue, JsError> {
+        to_js(&self.team)
+    }
+}
+
+#[wasm_bindgen(js_name = Game)]
+pub struct WasmGame {
+    game: Game,
+}
+
+#[wasm_bindgen(js_class = Game)]
+impl WasmGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new(home: &WasmTeam, away: &WasmTeam, day: usize) -> WasmGame {
+        WasmGame {
+            game: Game::new(home.team.clone(), away.team.clone(), day, SystemClock.now(), Rules::default()),
+        }
+    }
+
+    #[wasm_bindgen(js_name = withSeed)]
+    pub fn with_seed(home: &WasmTeam, away: &WasmTeam, day: usize, seed: i64) -> WasmGame {
+        WasmGame {
+            game: Game::with_seed(home.team.clone(), away.team.clone(), day, SystemClock.now(), Rules::default(), seed),
+        }
+    }
+
+    /// Advances the game by a single step and returns the events that happened.
+    pub fn step(&mut self) -> Result<JsValue, JsError> {
+        to_js(&self.game.step())
+    }
+
+    /// Runs the rest of the game.
+    pub fn simulate(&mut self) {
+        self.game.simulate_game();
+    }
+
+    /// Renders and removes every message that hasn't been read yet.
+    pub fn messages(&mut self) -> Vec<String> {
+        let mut lines = Vec::new();
+        while let Some((message, _)) = self.game.message_log.pop_front() {
+            lines.push(message.message_line());
+        }
+        lines
+    }
+
+    /// Returns every event that's happened so far.
+    pub fn events(&self) -> Result<JsValue, JsError> {
+        to_js(&self.game.events)
+    }
+
+    /// Returns the box score for everything that's happened so far.
+    #[wasm_bindgen(js_name = boxScore)]
+    pub fn box_score(&self) -> Result<JsValue, JsError> {
+        to_js(&self.game.box_score())
+    }
+
+    #[wasm_bindgen(getter, js_name = isOver)]
+    pub fn is_over(&self) -> bool {
+        self.game.is_over()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.game.get_game_name()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn inning(&self) -> u32 {
+        self.game.inning
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn top(&self) -> bool {
+        self.game.top
+    }
+
+    #[wasm_bindgen(getter, js_name = homeScore)]
+    pub fn home_score(&self) -> f64 {
+        self.game.scores.0
+    }
+
+    #[wasm_bindgen(getter, js_name = awayScore)]
+    pub fn away_score(&self) -> f64 {
+        self.game.scores.1
+    }
+
+    /// The home team as it is now, with any changes the game made to it.
+    #[wasm_bindgen(js_name = homeTeam)]
+    pub fn home_team(&self) -> WasmTeam {
+        WasmTeam {
+            team: self.game.home.clone(),
+        }
+    }
+
+    /// The away team as it is now, with any changes the game made to it.
+    #[wasm_bindgen(js_name = awayTeam)]
+    pub fn away_team(&self) -> WasmTeam {
+        WasmTeam {
+            team: self.game.away.clone(),
+        }
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+#[wasm_bindgen_test::wasm_bindgen_test]
+fn test_wasm_game() {
+    let home = WasmTeam::new(1, "Crabs".to_string(), "Baltimore".to_string(), "🦀".to_string(), 0, "BAL".to_string());
+    let away = WasmTeam::empty(2);
+    let mut game = WasmGame::with_seed(&home, &away, 1, 5);
+
+    let mut steps = 0;
+    while !game.is_over() {
+        let events = game.step().unwrap();
+        assert!(js_sys::Array::is_array(&events));
+        steps += 1;
+    }
+    assert!(steps > 0);
+    assert_ne!(game.home_score(), game.away_score());
+
+    let lines = game.messages();
+    assert_eq!(lines.first().map(String::as_str), Some("Blay pall!"));
+    assert!(game.messages().is_empty());
+
+    let events = js_sys::Array::from(&game.events().unwrap());
+    let first = events.get(0);
+    assert_eq!(js_sys::Reflect::get(&first, &"kind".into()).unwrap().as_string().as_deref(), Some("StartGame"));
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+#[wasm_bindgen_test::wasm_bindgen_test]
+fn test_wasm_clock() {
+    // SystemTime would panic here
+    assert!(SystemClock.now() > 0);
+}
+
+// Anything that hands back a JsValue only works on wasm32, so this sticks
+// to the bindings that return plain Rust values.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+#[test]
+fn test_wasm_bindings_natively() {
+    let home = WasmTeam::new(1, "Crabs".to_string(), "Baltimore".to_string(), "🦀".to_string(), 0, "BAL".to_string());
+    let away = WasmTeam::empty(2);
+    assert_eq!(home.name(), "Crabs");
+
+    // Plays the same game as the library does with the same seed
+    let mut game = WasmGame::with_seed(&home, &away, 1, 5);
+    let mut expected = Game::with_seed(home.team.clone(), away.team.clone(), 1, 0, Rules::default(), 5);
+    assert!(!game.is_over());
+    game.simulate();
+    expected.simulate_game();
+    assert!(game.is_over());
+    assert_eq!((game.home_score(), game.away_score()), expected.scores);
+    assert_eq!(game.name(), expected.get_game_name());
+
+    let lines = game.messages();
+    assert_eq!(lines, expected.message_log.messages.iter().map(|message| message.message_line()).collect::<Vec<String>>());
+    assert_eq!(lines.first().map(String::as_str), Some("Blay pall!"));
+    assert!(game.messages().is_empty());
+    assert_eq!(game.home_team().team.get_wins() + game.away_team().team.get_wins(), 1);
+}
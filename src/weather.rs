@@ -1,23 +1,53 @@
 // The goal of this file is to generate the weather conditions for use in the game.rs file.
-// Temporarily we will be using
+// Each weather carries its own RNG, so weather rolls don't change the
+// rolls the game itself makes.
 
 use crate::java_random::Random;
+use crate::rng::{GameRng, RngBackend};
 use crate::game::Game;
-use crate::messaging::Message;
+use crate::event::{EventKind, PlayerId};
 use crate::player::Player;
+use crate::player_flavor::PregameRitual;
 //use std::fmt;
 
+const WEATHER_COUNT: usize = 7;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Weather {
-    Clear(Random),
-    Crabs(Random),
-    Meownsoon(Random), // Oh my god it's back
-    PulsarPulsar(Random),
-    SolarEclipse(Random),
-    SnailMail(Random),
+    Clear(RngBackend),
+    Crabs(RngBackend),
+    Meownsoon(RngBackend), // Oh my god it's back
+    PulsarPulsar(RngBackend),
+    SolarEclipse(RngBackend),
+    SnailMail(RngBackend),
+    Birds(RngBackend),
 }
 
 impl Weather {
-    // Figure out some sort of set_seed function to make it easier to generate random numbers.
+    /// Picks a weather using the given seed. The same seed always gives the
+    /// same weather, and the weather's own rolls are seeded from it too.
+    pub fn from_seed(seed: i64) -> Self {
+        Weather::from_rng(RngBackend::Java(Random::new(seed)))
+    }
+
+    /// Picks a weather with `rng`, which the weather then keeps for its own rolls.
+    pub fn from_rng(mut rng: RngBackend) -> Self {
+        match (rng.next_f64() * WEATHER_COUNT as f64) as usize {
+            0 => Weather::Clear(rng),
+            1 => Weather::Crabs(rng),
+            2 => Weather::Meownsoon(rng),
+            3 => Weather::PulsarPulsar(rng),
+            4 => Weather::SolarEclipse(rng),
+            5 => Weather::SnailMail(rng),
+            _ => Weather::Birds(rng),
+        }
+    }
+
+    /// Picks a weather with a seed drawn from `rng`.
+    pub fn random(rng: &mut impl GameRng) -> Self {
+        Weather::from_seed(rng.next_i64())
+    }
 
     pub fn name(&self) -> &'static str {
         match self {
@@ -27,123 +57,174 @@ impl Weather {
             Weather::PulsarPulsar(_) => "Pulsar(Pulsar)",
             Weather::SolarEclipse(_) => "Solar Eclipse",
             Weather::SnailMail(_) => "Snail Mail",
+            Weather::Birds(_) => "Birds",
         }
     }
 
     pub fn description(&self) -> &'static str {
         match self {
-            // Placeholders for weather descriptions
             Weather::Clear(_) => "It's a beautiful day.",
-            Weather::Crabs(_) => "It's a beautiful day.",
-            Weather::Meownsoon(_) => "It's a beautiful day.",
-            Weather::PulsarPulsar(_) => "It's a beautiful day.",
-            Weather::SolarEclipse(_) => "It's a beautiful day.",
-            Weather::SnailMail(_) => "It's a beautiful day.",
+            Weather::Crabs(_) => "Crabs fill the field before every inning. Runners try to steal after every ball.",
+            // TODO: Meownsoon, Pulsar(Pulsar) and Snail Mail don't do
+            // anything yet. Their effects are waiting on the ALB code to port.
+            Weather::Meownsoon(_) => "Cats are in the forecast. Nothing's come of it yet.",
+            Weather::PulsarPulsar(_) => "Something out there is pulsing. Nobody's felt it yet.",
+            Weather::SolarEclipse(_) => "Rogue umpires incinerate players.",
+            Weather::SnailMail(_) => "The mail is running late. It hasn't arrived yet.",
+            Weather::Birds(_) => "Birds fill the stadium. The announcers have a lot to say about them.",
         }
     }
 
-    pub fn start_of_game(&self, game: &mut Game) {
+    pub fn start_of_game(&mut self, _game: &mut Game) {
         match self {
-            Weather::Clear => {},
+            Weather::Clear(_) => {},
             Weather::Crabs(_) => {},
-            Weather::Meownsoon => {},
-            Weather::PulsarPulsar => {},
+            Weather::Meownsoon(_) => {},
+            Weather::PulsarPulsar(_) => {},
             Weather::SolarEclipse(_) => {},
-            Weather::SnailMail => {},
+            Weather::SnailMail(_) => {},
+            Weather::Birds(_) => {},
         }
     }
 
     pub fn before_pitch(&mut self, game: &mut Game) {
         match self {
-            Weather::Clear => {},
+            Weather::Clear(_) => {},
             Weather::Crabs(_) => {},
-            Weather::Meownsoon => {},
-            Weather::PulsarPulsar => {},
+            Weather::Meownsoon(_) => {},
+            Weather::PulsarPulsar(_) => {},
             Weather::SolarEclipse(rng) => {
                 if rng.next_f64() > 0.00003 {
                     return;
                 }
-                let rand;
-                if game.are_bases_empty() {
-                    rand = (rng.next_f64() * 3.) as usize;
+                let rand = if game.are_bases_empty() {
+                    (rng.next_f64() * 3.) as usize
                 } else {
-                    rand = (rng.next_f64() * 4.) as usize;
-                }
-                let p;
-                // add statistic
-                match rand {
-                    0 => {
-                        // i've put myself into this hell
-                        let pitcher = game.pitchers_pitching().clone();
-                        for p in game.pitchers_mut() {
-                            if p == &pitcher {
-                                pitcher = Player::default(rng);
-                            }
-                        }
-
-                    }
+                    (rng.next_f64() * 4.) as usize
+                };
+                // The batter's the most exposed, so they take up two of the slots.
+                let victim = match rand {
+                    0 => game.pitchers_pitching().id,
+                    1 | 2 => game.batter.id,
+                    _ => {
+                        let runners: Vec<u32> = game.bases.iter().flatten().map(|player| player.id).collect();
+                        runners[(rng.next_f64() * runners.len() as f64) as usize]
+                    },
+                };
+                let replacement = Player::default(rng);
+                game.incinerate(victim, replacement);
+            },
+            Weather::SnailMail(_) => {},
+            Weather::Birds(rng) => {
+                if rng.next_f64() > 0.02 {
+                    return;
                 }
+                let bird = Bird::random(rng, game);
+                game.emit(EventKind::Birds { bird });
             },
-            Weather::SnailMail => {},
         }
     }
 
-    pub fn before_full_inning(&self, game: &mut Game) {
+    pub fn before_full_inning(&mut self, game: &mut Game) {
         match self {
-            Weather::Clear => {},
+            Weather::Clear(_) => {},
             Weather::Crabs(_) => {
-                game.log(Message::Crabs);
+                game.emit(EventKind::Crabs);
             },
-            Weather::Meownsoon => {},
-            Weather::PulsarPulsar => {},
-            Weather::SnailMail => {},
+            Weather::Meownsoon(_) => {},
+            Weather::PulsarPulsar(_) => {},
+            Weather::SolarEclipse(_) => {},
+            Weather::SnailMail(_) => {},
+            Weather::Birds(_) => {},
         }
     }
 
-    pub fn before_half_inning(&self, game: &mut Game) {
+    pub fn before_half_inning(&mut self, _game: &mut Game) {
         match self {
-            Weather::Clear => {},
+            Weather::Clear(_) => {},
             Weather::Crabs(_) => {},
-            Weather::Meownsoon => {},
-            Weather::PulsarPulsar => {},
-            Weather::SnailMail => {},
+            Weather::Meownsoon(_) => {},
+            Weather::PulsarPulsar(_) => {},
+            Weather::SolarEclipse(_) => {},
+            Weather::SnailMail(_) => {},
+            Weather::Birds(_) => {},
         }
     }
 
-    pub fn after_ball(&self, game: &mut Game) {
+    pub fn after_ball(&mut self, game: &mut Game) {
         match self {
-            Weather::Clear => {},
+            Weather::Clear(_) => {},
             Weather::Crabs(_) => {
+                // Lead runner first, so the ones behind them have
+                // somewhere to go
                 for i in (0..game.bases.len()).rev() {
-                    if game.bases[i].is_some() {
+                    if game.can_steal(i) {
                         game.steal_attempt(i);
                     }
                 }
             },
-            Weather::Meownsoon => {},
-            Weather::PulsarPulsar => {},
-            Weather::SnailMail => {},
+            Weather::Meownsoon(_) => {},
+            Weather::PulsarPulsar(_) => {},
+            Weather::SolarEclipse(_) => {},
+            Weather::SnailMail(_) => {},
+            Weather::Birds(_) => {},
         }
     }
 
-    pub fn end_of_game(&self, game: &mut Game) {
+    pub fn end_of_game(&mut self, _game: &mut Game) {
         match self {
-            Weather::Clear => {},
-            Weather::Crabs => {},
-            Weather::Meownsoon => {},
-            Weather::PulsarPulsar => {},
-            Weather::SnailMail => {},
+            Weather::Clear(_) => {},
+            Weather::Crabs(_) => {},
+            Weather::Meownsoon(_) => {},
+            Weather::PulsarPulsar(_) => {},
+            Weather::SolarEclipse(_) => {},
+            Weather::SnailMail(_) => {},
+            Weather::Birds(_) => {},
         }
     }
 }
 
+/// A bird line, picked by `Bird::random()`. `numbers` fill in the lines
+/// that count birds, and `player` is who line 23 is about.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bird {
-
+    pub line: usize,
+    pub numbers: (i32, i32),
+    pub player: Option<PlayerId>,
 }
 
 impl Bird {
-    fn BirdMessage(n: i32, mut random: Random) -> String {
-        match n {
+    pub const LINES: usize = 29;
+
+    /// Picks a random bird line. Only draws the numbers and the player if the
+    /// line needs them.
+    pub fn random(rng: &mut impl GameRng, game: &Game) -> Self {
+        let line = (rng.next_f64() * Bird::LINES as f64) as usize;
+        let numbers = match line {
+            12 => ((rng.next_f64() * 1000.0).trunc() as i32, 0),
+            15 => ((rng.next_f64() * 1000.0).trunc() as i32, (rng.next_f64() * 1000.0).trunc() as i32),
+            _ => (0, 0),
+        };
+        let player = match line {
+            23 => {
+                // Random active player
+                let players: Vec<PlayerId> = game.teams().flat_map(|team| team.active_players()).map(|player| player.id).collect();
+                Some(players[(rng.next_f64() * players.len() as f64) as usize])
+            },
+            _ => None,
+        };
+        Bird {
+            line,
+            numbers,
+            player,
+        }
+    }
+
+    /// Renders the line. `player` is the name and pregame ritual of the
+    /// player line 23 is about.
+    pub fn message_line(&self, player: Option<(&str, &PregameRitual)>) -> String {
+        match self.line {
         0 => "Seeing a lot of birds today.".to_string(),
         1 => "The birds continue to stare.".to_string(),
         2 => "[BIRD NOISES]".to_string(),
@@ -156,10 +237,10 @@ impl Bird {
         9 => "The birds stare into the sun.".to_string(),
         10 => "The birds give you the shivers.".to_string(),
         11 => "Birds.".to_string(),
-        12 => format!("{} birds.", (random.next_f64() * 1000.0).trunc() as i32),
+        12 => format!("{} birds.", self.numbers.0),
         13 => "Something isn't right about these birds.".to_string(),
         14 => "A bird pecks the outfield for features.".to_string(),
-        15 => format!("A rogue umpire incinerated Bird {}!\nThey're replaced by Bird {}.", (random.next_f64() * 1000.0).trunc() as i32, (random.next_f64() * 1000.0).trunc() as i32),
+        15 => format!("A rogue umpire incinerated Bird {}!\nThey're replaced by Bird {}.", self.numbers.0, self.numbers.1),
         16 => "Is that a normal number of eyes for a bird?".to_string(),
         17 => "Another bird lands in the rafters.".to_string(),
         18 => "I hate these birds.".to_string(),
@@ -167,22 +248,20 @@ impl Bird {
         20 => "The birds form a blanket over the stadium, blocking out the sun.".to_string(),
         21 => "The birds squak of death.".to_string(),
         22 => "Too many birds.".to_string(),
-        23 => "not implemented yet".to_string(),
-                /*don't ruin the surprise for this one!
-                Player player = randomActivePlayer();
-                if(player.pregameRitual.equals("Eating a bird"))
-                    println(player.name + " eats a bird!");
-                else if(player.pregameRitual.equals("Eating multiple birds"))
-                    println(player.name + " gobbles down an ungodly amounts of birds!");
-                else if(player.pregameRitual.equals("Shaking their fist at a bird's nest"))
-                    println(player.name + " REALLY hates birds!");
-                else if(player.pregameRitual.equals("Birdwatching"))
-                    println(player.name + " watches the birds.");
-                else if(player.pregameRitual.equals("Feeding the birds"))
-                    println("The birds feed off of " + player.name + ".");
-                else
-                    println(player.name + " is thinking about birds.");
-                break;*/
+        23 => {
+            // don't ruin the surprise for this one!
+            let Some((name, ritual)) = player else {
+                return "ERROR: BIRDS".to_string();
+            };
+            match ritual.as_str() {
+                "Eating a bird" => format!("{} eats a bird!", name),
+                "Eating multiple birds" => format!("{} gobbles down an ungodly amounts of birds!", name),
+                "Shaking their fist at a bird's nest" => format!("{} REALLY hates birds!", name),
+                "Birdwatching" => format!("{} watches the birds.", name),
+                "Feeding the birds" => format!("The birds feed off of {}.", name),
+                _ => format!("{} is thinking about birds.", name),
+            }
+        },
         24 => "You spot a shiny bird! It looks back at you, annoyed.".to_string(),
         25 => "The cacophony of bird calls makes you sick.".to_string(),
         26 => "The birds stare at their favorite Keeper.".to_string(),
@@ -192,3 +271,152 @@ impl Bird {
         }
     }
 }
+
+#[test]
+fn test_bird_ritual_line() {
+    let bird = Bird {
+        line: 23,
+        numbers: (0, 0),
+        player: Some(0),
+    };
+    let eater = PregameRitual::new_from_str("Eating multiple birds".to_string());
+    let singer = PregameRitual::new_from_str("Singing".to_string());
+
+    assert_eq!(bird.message_line(Some(("Wyatt Mason", &eater))), "Wyatt Mason gobbles down an ungodly amounts of birds!");
+    assert_eq!(bird.message_line(Some(("Wyatt Mason", &singer))), "Wyatt Mason is thinking about birds.");
+}
+
+#[test]
+fn test_weather_selection() {
+    use std::collections::BTreeMap;
+    use crate::rng::RngKind;
+    use crate::seed::{Seed, Subsystem};
+
+    // Every weather comes up about as often as the others, with either kind
+    // of RNG. Games seed it the same way. Seeds right next to each other
+    // would start a Java Random off with rolls that are too much alike.
+    for kind in [RngKind::Java, RngKind::Counter] {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for game in 0..7000 {
+            let seed = Seed::new(0).game(game).subsystem(Subsystem::Weather).value();
+            *counts.entry(Weather::from_rng(kind.seeded(seed)).name()).or_default() += 1;
+        }
+        assert_eq!(counts.len(), WEATHER_COUNT, "{:?}", counts);
+        assert!(counts.values().all(|count| (850..=1150).contains(count)), "{:?}", counts);
+    }
+
+    // The same seed always picks the same weather, which rolls the same afterwards
+    assert_eq!(format!("{:?}", Weather::from_seed(21)), format!("{:?}", Weather::from_seed(21)));
+}
+
+#[test]
+fn test_weather_hooks_fire() {
+    use crate::rules::Rules;
+    use crate::team::Team;
+
+    let play = |weather: Weather| -> Game {
+        let mut rng = Random::new(11);
+        let (home, away) = (Team::empty(&mut rng), Team::empty(&mut rng));
+        let mut game = Game::with_rng(home, away, 1, 0, Rules::default(), RngBackend::Java(Random::new(11)), weather);
+        game.simulate_game();
+        game
+    };
+    let weather_events = |game: &Game| -> Vec<EventKind> {
+        game.events.iter()
+            .filter(|event| matches!(event.kind, EventKind::Crabs | EventKind::Birds { .. } | EventKind::Incineration { .. }))
+            .map(|event| event.kind.clone())
+            .collect()
+    };
+
+    // Crabs come before every full inning
+    let crabs = play(Weather::Crabs(RngBackend::Java(Random::new(1))));
+    let innings = crabs.events.iter().filter(|event| event.kind == EventKind::InningStart && event.top).count();
+    assert_eq!(weather_events(&crabs).iter().filter(|kind| **kind == EventKind::Crabs).count(), innings);
+
+    // Birds show up now and then, and the players they talk about are in the game
+    let birds = play(Weather::Birds(RngBackend::Java(Random::new(1))));
+    let lines = weather_events(&birds);
+    assert!(!lines.is_empty());
+    for kind in lines {
+        let EventKind::Birds { bird } = kind else {
+            panic!("{:?} in Birds", kind);
+        };
+        assert!(bird.line < Bird::LINES);
+        if let Some(player) = bird.player {
+            assert!(birds.find_player(player).is_some());
+        }
+    }
+
+    // The rest don't do anything yet
+    for weather in [Weather::Clear, Weather::Meownsoon, Weather::PulsarPulsar, Weather::SnailMail] {
+        let game = play(weather(RngBackend::Java(Random::new(1))));
+        assert!(weather_events(&game).is_empty(), "{}", game.weather.name());
+    }
+
+    // Every weather tells you what it is, even the ones that don't do anything
+    let all = [
+        Weather::Clear, Weather::Crabs, Weather::Meownsoon, Weather::PulsarPulsar,
+        Weather::SolarEclipse, Weather::SnailMail, Weather::Birds,
+    ];
+    let descriptions: std::collections::HashSet<_> = all.iter()
+        .map(|weather| weather(RngBackend::default()).description())
+        .collect();
+    assert_eq!(descriptions.len(), WEATHER_COUNT);
+}
+
+#[test]
+fn test_birds_only_talk_about_active_players() {
+    use crate::rules::Rules;
+    use crate::team::{Roster, Team};
+
+    let mut rng = Random::new(12);
+    let mut home = Team::empty(&mut rng);
+    let away = Team::empty(&mut rng);
+    let mut shadows = Vec::new();
+    for _ in 0..20 {
+        let shadow = Player::default(&mut rng);
+        shadows.push(shadow.id);
+        home.add_player(Roster::Shadows, shadow).unwrap();
+    }
+    let game = Game::new(home, away, 1, 0, Rules::default());
+
+    let mut talked_about = 0;
+    while talked_about < 100 {
+        let bird = Bird::random(&mut rng, &game);
+        if let Some(player) = bird.player {
+            assert!(!shadows.contains(&player));
+            talked_about += 1;
+        }
+    }
+}
+
+#[test]
+fn test_crabs_steals_never_lose_runners() {
+    use crate::player_stats::CharacterStat;
+    use crate::rules::Rules;
+    use crate::team::Team;
+
+    let mut rng = Random::new(15);
+    let mut game = Game::new(Team::empty(&mut rng), Team::empty(&mut rng), 1, 0, Rules::default());
+    game.step();
+
+    // The lead runner never wants to go, and the one behind them always does
+    let mut lead = Player::default(&mut rng);
+    lead.arrogance = CharacterStat::new(-100.0);
+    let mut trailing = Player::default(&mut rng);
+    trailing.arrogance = CharacterStat::new(100.0);
+    trailing.dexterity = CharacterStat::new(100.0);
+    game.bases = vec![Some(trailing.clone()), Some(lead.clone()), None];
+
+    let mut crabs = Weather::Crabs(RngBackend::default());
+    crabs.after_ball(&mut game);
+    assert_eq!(game.bases, vec![Some(trailing.clone()), Some(lead.clone()), None]);
+
+    // Nobody runs once the at-bat's over
+    game.bases = vec![Some(trailing.clone()), None, None];
+    game.strikes = 100;
+    let events = game.events.len();
+    crabs.after_ball(&mut game);
+    assert_eq!(game.bases, vec![Some(trailing), None, None]);
+    assert_eq!(game.events.len(), events);
+}